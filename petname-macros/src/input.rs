@@ -1,24 +1,21 @@
 use syn::parse::{Parse, ParseStream};
 use syn::{Ident, LitStr, Token};
 
-/// Input to the macros in this crate.
+/// Arguments to the macros in this crate, before they are checked.
 ///
-/// An optional unnamed directory prefix may be followed by named arguments for
-/// each word list. Individual paths are resolved relative to the directory if
-/// one is given, or used directly otherwise.
-pub struct PetnamesInput {
-    pub dir: Option<LitStr>,
-    pub adjectives: Option<LitStr>,
-    pub adverbs: Option<LitStr>,
-    pub nouns: Option<LitStr>,
+/// An optional unnamed directory prefix may be followed by named arguments
+/// and flags, which are names on their own.
+struct Arguments {
+    dir: Option<LitStr>,
+    named: Vec<(Ident, LitStr)>,
+    flags: Vec<Ident>,
 }
 
-impl Parse for PetnamesInput {
+impl Parse for Arguments {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut dir = None;
-        let mut adjectives = None;
-        let mut adverbs = None;
-        let mut nouns = None;
+        let mut named = Vec::new();
+        let mut flags = Vec::new();
 
         // Optional unnamed directory argument.
         if input.peek(LitStr) {
@@ -28,18 +25,68 @@ impl Parse for PetnamesInput {
             }
         }
 
-        // Named arguments in any order.
+        // Named arguments and flags in any order.
         while !input.is_empty() {
             let name: Ident = input.parse()?;
-            input.parse::<Token![=]>()?;
-            let value: LitStr = input.parse()?;
-            match name.to_string().as_str() {
-                "adjectives" if adjectives.is_none() => adjectives = Some(value),
-                "adverbs" if adverbs.is_none() => adverbs = Some(value),
-                "nouns" if nouns.is_none() => nouns = Some(value),
-                "adjectives" | "adverbs" | "nouns" => {
-                    return Err(syn::Error::new(name.span(), format!("duplicate argument `{name}`")));
+            if named.iter().map(|(seen, _): &(Ident, LitStr)| seen).chain(&flags).any(|seen| *seen == name) {
+                return Err(syn::Error::new(name.span(), format!("duplicate argument `{name}`")));
+            }
+            if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                named.push((name, input.parse()?));
+            } else {
+                flags.push(name);
+            }
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Arguments { dir, named, flags })
+    }
+}
+
+/// Input to the macros in this crate.
+///
+/// An optional unnamed directory prefix may be followed by named arguments for
+/// each word list. Individual paths are resolved relative to the directory if
+/// one is given, or used directly otherwise. The `weighted` flag means that
+/// words may carry weights, as in `word:weight`.
+pub struct PetnamesInput {
+    pub dir: Option<LitStr>,
+    pub adjectives: Option<LitStr>,
+    pub adverbs: Option<LitStr>,
+    pub nouns: Option<LitStr>,
+    pub weighted: bool,
+}
+
+impl TryFrom<Arguments> for PetnamesInput {
+    type Error = syn::Error;
+
+    fn try_from(arguments: Arguments) -> syn::Result<Self> {
+        let mut input = PetnamesInput {
+            dir: arguments.dir,
+            adjectives: None,
+            adverbs: None,
+            nouns: None,
+            weighted: false,
+        };
+        for flag in arguments.flags {
+            match flag.to_string().as_str() {
+                "weighted" => input.weighted = true,
+                other => {
+                    return Err(syn::Error::new(
+                        flag.span(),
+                        format!("unexpected flag `{other}`, expected `weighted`"),
+                    ));
                 }
+            }
+        }
+        for (name, value) in arguments.named {
+            match name.to_string().as_str() {
+                "adjectives" => input.adjectives = Some(value),
+                "adverbs" => input.adverbs = Some(value),
+                "nouns" => input.nouns = Some(value),
                 other => {
                     return Err(syn::Error::new(
                         name.span(),
@@ -49,11 +96,77 @@ impl Parse for PetnamesInput {
                     ));
                 }
             }
-            if input.peek(Token![,]) {
-                input.parse::<Token![,]>()?;
+        }
+        Ok(input)
+    }
+}
+
+impl Parse for PetnamesInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Arguments>()?.try_into()
+    }
+}
+
+/// Input to the `english!` macro when given `slots`: a grammar of any number
+/// of named word lists.
+///
+/// Besides the optional directory prefix and `slots`, each named argument is
+/// the path of the word list of that name. The path of a list without an
+/// argument is its name with a `.txt` extension.
+pub struct GrammarInput {
+    pub dir: Option<LitStr>,
+    pub slots: String,
+    /// The name of each list used by the slots, in order of first use, and its
+    /// path if given.
+    pub lists: Vec<(String, Option<LitStr>)>,
+}
+
+impl GrammarInput {
+    fn try_from(arguments: Arguments, slots: String) -> syn::Result<Self> {
+        // The slots themselves are checked where the macro expands, by
+        // `Grammar::check_slots`, so that they are parsed in one place. Only
+        // the names of their lists are needed here.
+        let mut lists: Vec<(String, Option<LitStr>)> = Vec::new();
+        for slot in slots.split_ascii_whitespace() {
+            let name = slot.strip_suffix('*').unwrap_or(slot);
+            if !name.is_empty() && !lists.iter().any(|(seen, _)| seen == name) {
+                lists.push((name.to_owned(), None));
+            }
+        }
+        if let Some(flag) = arguments.flags.first() {
+            return Err(syn::Error::new(flag.span(), format!("unexpected flag `{flag}` with `slots`")));
+        }
+        for (name, value) in arguments.named {
+            if name == "slots" {
+                continue;
+            }
+            match lists.iter_mut().find(|(list, _)| name == list) {
+                Some((_, path)) => *path = Some(value),
+                None => {
+                    return Err(syn::Error::new(name.span(), format!("no slot uses the list `{name}`")));
+                }
             }
         }
+        Ok(GrammarInput { dir: arguments.dir, slots, lists })
+    }
+}
+
+/// Input to the `english!` macro: word lists for `Petnames`, or, when given
+/// `slots`, for a `Grammar`.
+pub enum EnglishInput {
+    Petnames(PetnamesInput),
+    Grammar(GrammarInput),
+}
 
-        Ok(PetnamesInput { dir, adjectives, adverbs, nouns })
+impl Parse for EnglishInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let arguments: Arguments = input.parse()?;
+        match arguments.named.iter().find(|(name, _)| name == "slots") {
+            Some((_, slots)) => {
+                let slots = slots.value();
+                GrammarInput::try_from(arguments, slots).map(EnglishInput::Grammar)
+            }
+            None => arguments.try_into().map(EnglishInput::Petnames),
+        }
     }
 }
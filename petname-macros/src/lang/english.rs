@@ -1,32 +1,47 @@
 use proc_macro::TokenStream;
 use std::path::{Path, PathBuf};
 
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
+use syn::Ident;
 
-use crate::{input::PetnamesInput, paths::PetnamesPaths, text::read_and_process};
+use crate::{
+    input::{EnglishInput, GrammarInput, PetnamesInput},
+    paths::PetnamesPaths,
+    text::{read_and_process, read_and_process_weighted},
+};
 
 /// See [`english!`][`crate::english!`] for documentation.
 pub fn expand(input: TokenStream) -> TokenStream {
-    let input: PetnamesInput = syn::parse(input).expect("petnames! parse error");
+    let input: EnglishInput = syn::parse(input).expect("petnames! parse error");
 
     let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
     let manifest_path = PathBuf::from(&manifest_dir);
-    let paths = PetnamesPaths::from(input).resolve(&manifest_path);
+
+    match input {
+        EnglishInput::Petnames(input) if input.weighted => expand_weighted(input, &manifest_path),
+        EnglishInput::Petnames(input) => expand_petnames(input, &manifest_path),
+        EnglishInput::Grammar(input) => expand_grammar(input, &manifest_path),
+    }
+    .into()
+}
+
+fn path_str<'a>(path: &'a Path, name: &str) -> &'a str {
+    path.to_str().unwrap_or_else(|| panic!("{name} path not UTF-8: {}", path.display()))
+}
+
+fn expand_petnames(input: PetnamesInput, manifest_path: &Path) -> TokenStream2 {
+    let paths = PetnamesPaths::from(input).resolve(manifest_path);
 
     let (adj_words, adj_count) = read_and_process(&paths.adjectives);
     let (adv_words, adv_count) = read_and_process(&paths.adverbs);
     let (noun_words, noun_count) = read_and_process(&paths.nouns);
 
-    fn path_str<'a>(path: &'a Path, name: &'static str) -> &'a str {
-        path.to_str().unwrap_or_else(|| panic!("{name} path not UTF-8: {}", path.display()))
-    }
-
     let adj_path = path_str(&paths.adjectives, "adjectives");
     let adv_path = path_str(&paths.adverbs, "adverbs");
     let noun_path = path_str(&paths.nouns, "nouns");
 
-    let expanded: TokenStream2 = quote! {
+    quote! {
         {
             // This macro reads word list files via `std::fs`, but the compiler
             // doesn't track those reads. These `include_str!(…)` calls register
@@ -46,7 +61,79 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 nouns: ::alloc::borrow::Cow::Borrowed(&NOUNS[..]),
             }
         }
-    };
+    }
+}
+
+fn expand_weighted(input: PetnamesInput, manifest_path: &Path) -> TokenStream2 {
+    let paths = PetnamesPaths::from(input).resolve(manifest_path);
+
+    let (adj_words, adj_weights, adj_count) = read_and_process_weighted(&paths.adjectives);
+    let (adv_words, adv_weights, adv_count) = read_and_process_weighted(&paths.adverbs);
+    let (noun_words, noun_weights, noun_count) = read_and_process_weighted(&paths.nouns);
+
+    let adj_path = path_str(&paths.adjectives, "adjectives");
+    let adv_path = path_str(&paths.adverbs, "adverbs");
+    let noun_path = path_str(&paths.nouns, "nouns");
+
+    quote! {
+        {
+            // See `expand_petnames` about `include_str!` being used purely to
+            // register these files as rebuild dependencies.
+            const _: &'static str = include_str!(#adj_path);
+            const _: &'static str = include_str!(#adv_path);
+            const _: &'static str = include_str!(#noun_path);
+            static ADJECTIVES: [&'static str; #adj_count] = [ #( #adj_words ),* ];
+            static ADVERBS: [&'static str; #adv_count] = [ #( #adv_words ),* ];
+            static NOUNS: [&'static str; #noun_count] = [ #( #noun_words ),* ];
+            static ADJECTIVE_WEIGHTS: [f64; #adj_count] = [ #( #adj_weights ),* ];
+            static ADVERB_WEIGHTS: [f64; #adv_count] = [ #( #adv_weights ),* ];
+            static NOUN_WEIGHTS: [f64; #noun_count] = [ #( #noun_weights ),* ];
+            ::petname::Weighted::with_weights(
+                ::petname::Petnames {
+                    adjectives: ::petname::Words::Borrowed(&ADJECTIVES[..]),
+                    adverbs: ::petname::Words::Borrowed(&ADVERBS[..]),
+                    nouns: ::petname::Words::Borrowed(&NOUNS[..]),
+                },
+                &ADJECTIVE_WEIGHTS[..],
+                &ADVERB_WEIGHTS[..],
+                &NOUN_WEIGHTS[..],
+            )
+            .expect("weights checked by english!")
+        }
+    }
+}
+
+fn expand_grammar(input: GrammarInput, manifest_path: &Path) -> TokenStream2 {
+    let dir = input.dir.as_ref().map(|dir| PathBuf::from(dir.value()));
+    let slots = &input.slots;
 
-    expanded.into()
+    let (embeds, lists): (Vec<TokenStream2>, Vec<TokenStream2>) = input
+        .lists
+        .iter()
+        .enumerate()
+        .map(|(position, (name, path))| {
+            let path = path.as_ref().map_or_else(|| format!("{name}.txt"), |path| path.value());
+            let path =
+                manifest_path.join(dir.as_ref().map_or_else(|| PathBuf::from(&path), |dir| dir.join(&path)));
+            let (words, count) = read_and_process(&path);
+            let path = path_str(&path, name);
+            let ident = Ident::new(&format!("LIST_{position}"), Span::call_site());
+            let embed = quote! {
+                const _: &'static str = include_str!(#path);
+                static #ident: [&'static str; #count] = [ #( #words ),* ];
+            };
+            (embed, quote! { .with_list(#name, &#ident[..]) })
+        })
+        .unzip();
+
+    quote! {
+        {
+            // See `expand_petnames` about `include_str!` being used purely to
+            // register these files as rebuild dependencies.
+            #( #embeds )*
+            // Fails to compile if the slots are not valid.
+            const _: () = ::petname::Grammar::check_slots(#slots);
+            ::petname::Grammar::new(#slots).expect("slots checked by english!") #( #lists )*
+        }
+    }
 }
@@ -16,6 +16,7 @@ use crate::{
 /// See [`turkish!`][`crate::turkish!`] for documentation.
 pub fn expand(input: TokenStream) -> TokenStream {
     let input: PetnamesInput = syn::parse(input).expect("turkish! parse error");
+    assert!(!input.weighted, "turkish! does not support weighted word lists");
 
     let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
     let manifest_path = PathBuf::from(&manifest_dir);
@@ -60,6 +60,24 @@ mod text;
 /// let p = petname::petnames!();
 /// ```
 ///
+/// Given the `weighted` flag, it will instead construct a `petname::Weighted`.
+/// Each word in the files may then be followed by a colon and its weight, e.g.
+/// `fox:2.5`; a word without one has a weight of 1:
+///
+/// ```ignore
+/// let w = petname::english!("words/common", weighted);
+/// ```
+///
+/// Given `slots`, it will instead construct a `petname::Grammar` with any
+/// number of named word lists; see `Grammar::new` for the format of `slots`.
+/// Each other named argument is then the path of the list of that name, and
+/// the path of a list without one is its name with a `.txt` extension. This
+/// will look for `color.txt` and `animals.txt` in the `words/other` directory:
+///
+/// ```ignore
+/// let g = petname::english!("words/other", slots = "color animal", animal = "animals.txt");
+/// ```
+///
 #[proc_macro]
 pub fn english(input: TokenStream) -> TokenStream {
     crate::lang::english::expand(input)
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 pub fn read_and_process(path: &Path) -> (Vec<String>, usize) {
@@ -9,6 +9,30 @@ pub fn read_and_process(path: &Path) -> (Vec<String>, usize) {
     (words, count)
 }
 
+/// Like [`read_and_process`], but each word may be followed by a colon and its
+/// weight, e.g. `fox:2.5`, else its weight is 1. The first weight given for a
+/// word is used.
+pub fn read_and_process_weighted(path: &Path) -> (Vec<String>, Vec<f64>, usize) {
+    let contents =
+        std::fs::read_to_string(path).unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+    let mut weights: HashMap<&str, f64> = HashMap::new();
+    for token in word_tokens(&contents) {
+        let (word, weight) = match token.rsplit_once(':') {
+            Some((word, weight)) => match weight.parse::<f64>() {
+                Ok(weight) if weight.is_finite() && weight >= 0.0 => (word, weight),
+                _ => panic!("invalid weight {token:?} in {}", path.display()),
+            },
+            None => (token, 1.0),
+        };
+        weights.entry(word).or_insert(weight);
+    }
+    let mut weights: Vec<(&str, f64)> = weights.into_iter().collect();
+    weights.sort_by_key(|(word, _)| *word);
+    let count = weights.len();
+    let (words, weights) = weights.into_iter().map(|(word, weight)| (word.to_owned(), weight)).unzip();
+    (words, weights, count)
+}
+
 fn split_words_deduplicate_and_sort(input: &str) -> Vec<String> {
     let words = word_tokens(input).collect::<HashSet<_>>();
     let mut words: Vec<String> = words.into_iter().map(|s| s.to_owned()).collect();
@@ -0,0 +1,196 @@
+//! Batches of names that are all different enough from each other to tell
+//! apart, as made by [`Namer::distinct_batch`].
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{Generator, Namer};
+
+/// How many names in a row may be rejected, for being too similar to names
+/// already in the batch, before giving up.
+const ATTEMPTS: usize = 1000;
+
+impl<'a, G: Generator + ?Sized> Namer<'a, G> {
+    /// Prepare to generate a batch of `n` names, every pair of which is at
+    /// least `min_distance` edits apart; see [`DistinctBatch`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-rng")] {
+    /// let petnames = petname::Petnames::new("brave calm", "", "box fox otter otters");
+    /// let namer = petnames.namer(2, "-");
+    /// let names = namer.distinct_batch(3, 2).generate(&mut rand::rng()).unwrap();
+    /// assert_eq!(names.len(), 3);
+    /// // Not enough names are 2 edits apart from each other.
+    /// assert!(namer.distinct_batch(5, 2).generate(&mut rand::rng()).is_err());
+    /// # }
+    /// ```
+    ///
+    pub fn distinct_batch(&self, n: usize, min_distance: usize) -> DistinctBatch<'_, 'a, G> {
+        DistinctBatch { namer: self, n, min_distance, different_first_words: false }
+    }
+
+    /// Push a name onto `buf`, as [`generate_into`][`Self::generate_into`]
+    /// does, and return its first word, before any style is applied.
+    fn generate_with_first_word(&self, buf: &mut String, rng: &mut dyn rand::Rng) -> String {
+        let mut name = String::new();
+        self.generate_words_into(&mut name, rng, self.separator);
+        let ranges = match self.style {
+            Some((_, ref word_finder)) => word_finder(&name),
+            None => self.generator.word_ranges(&name, self.words, self.separator),
+        };
+        let first_word = match ranges.first() {
+            Some(range) => name[range.clone()].into(),
+            None => String::new(),
+        };
+        let start = buf.len();
+        self.generate_styled(buf, |buf| buf.push_str(&name));
+        if let Some(suffix) = self.suffix_for(buf, start) {
+            suffix.generate_into(buf, rng);
+        }
+        first_word
+    }
+}
+
+/// A batch of names that are easy to tell apart: every pair of names is at
+/// least some number of edits – insertions, deletions, or substitutions of a
+/// character – apart, and, optionally, has different first words.
+///
+/// Created by [`Namer::distinct_batch`]. Names are drawn at random and kept
+/// if they are different enough from those kept already, until there are
+/// enough, or until so many in a row are too similar that there are probably
+/// not enough to be found.
+///
+pub struct DistinctBatch<'n, 'a, G: ?Sized> {
+    namer: &'n Namer<'a, G>,
+    n: usize,
+    min_distance: usize,
+    different_first_words: bool,
+}
+
+impl<G: Generator + ?Sized> DistinctBatch<'_, '_, G> {
+    /// Also require every name to begin with a different word, e.g. not both
+    /// `brave-fox` and `brave-otter`.
+    pub fn different_first_words(self) -> Self {
+        Self { different_first_words: true, ..self }
+    }
+
+    /// Generate the batch of names, in the order they were drawn.
+    pub fn generate(&self, rng: &mut dyn rand::Rng) -> Result<Vec<String>, BatchError> {
+        let mut names: Vec<String> = Vec::with_capacity(self.n);
+        let mut first_words: Vec<String> = Vec::with_capacity(self.n);
+        let mut rejected = 0;
+        while names.len() < self.n {
+            let mut name = String::new();
+            let first_word = self.namer.generate_with_first_word(&mut name, rng);
+            if name.is_empty() || rejected >= ATTEMPTS {
+                return Err(BatchError::TooFew { found: names.len(), wanted: self.n });
+            } else if self.namer.is_excluded(&name)
+                || (self.different_first_words && first_words.contains(&first_word))
+                || names.iter().any(|other| edit_distance(other, &name) < self.min_distance)
+            {
+                rejected += 1;
+            } else {
+                rejected = 0;
+                names.push(name);
+                first_words.push(first_word);
+            }
+        }
+        Ok(names)
+    }
+}
+
+/// The reason that a [`DistinctBatch`] could not be generated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchError {
+    /// Only `found` names different enough from each other could be found,
+    /// out of the `wanted` names in the batch.
+    TooFew { found: usize, wanted: usize },
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew { found, wanted } => {
+                write!(f, "found only {found} of {wanted} names different enough from each other")
+            }
+        }
+    }
+}
+
+impl core::error::Error for BatchError {}
+
+/// The Levenshtein distance between `a` and `b`: the fewest insertions,
+/// deletions, or substitutions of a character that turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use rand::SeedableRng;
+
+    use super::{edit_distance, BatchError};
+    use crate::Petnames;
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("calm-otter", "calm-otters"), 1);
+        assert_eq!(edit_distance("brave-fox", "brave-box"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "fox"), 3);
+        assert_eq!(edit_distance("ünï", "uni"), 2);
+    }
+
+    #[test]
+    fn generate_keeps_names_apart() {
+        let petnames = Petnames::new("brave calm fair", "", "box fox otter otters");
+        let namer = petnames.namer(2, "-");
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names = namer.distinct_batch(4, 3).generate(&mut rng).unwrap();
+        for (position, name) in names.iter().enumerate() {
+            assert!(names[..position].iter().all(|other| edit_distance(other, name) >= 3), "{names:?}");
+        }
+    }
+
+    #[test]
+    fn generate_can_require_different_first_words() {
+        let petnames = Petnames::new("brave calm fair", "", "box fox otter otters");
+        let namer = petnames.namer(2, "").with_style(crate::Style::Pascal);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names = namer.distinct_batch(3, 0).different_first_words().generate(&mut rng).unwrap();
+        let mut firsts: Vec<&str> =
+            names.iter().map(|name| &name[..name[1..].find(char::is_uppercase).unwrap() + 1]).collect();
+        firsts.sort_unstable();
+        assert_eq!(firsts, ["Brave", "Calm", "Fair"]);
+        assert_eq!(
+            namer.distinct_batch(4, 0).different_first_words().generate(&mut rng),
+            Err(BatchError::TooFew { found: 3, wanted: 4 })
+        );
+    }
+
+    #[test]
+    fn generate_fails_without_names() {
+        let petnames = Petnames::new("", "", "");
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        assert_eq!(
+            petnames.namer(2, "-").distinct_batch(1, 0).generate(&mut rng),
+            Err::<Vec<String>, _>(BatchError::TooFew { found: 0, wanted: 1 })
+        );
+    }
+}
@@ -0,0 +1,733 @@
+//! Words, and pairs of words, that names must never contain.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, ops::Range};
+
+use rand::RngExt;
+
+use crate::{ByLetters, Generator, Indexed, Lengths, List, Lists, Namer, Petnames, WordFinder, Words};
+
+/// How many names to draw at random, looking for one that is not blocked,
+/// before falling back to choosing among the names that are left.
+const ATTEMPTS: usize = 16;
+
+/// Words, and pairs of words, that must never appear in a name, e.g. because
+/// they read as an insult together even when each is innocuous alone.
+///
+/// A blocked word is never used, nor may it be formed across the boundary
+/// between two words, as can happen when names are joined without a
+/// separator. A blocked pair is two words that must never be next to each
+/// other, in that order. Words are compared without regard to case.
+///
+/// Use [`Blocking`] to generate names that avoid a blocklist.
+///
+/// # Examples
+///
+/// ```rust
+/// let blocklist = petname::Blocklist::parse("hit\nlazy dog").unwrap();
+/// assert!(blocklist.blocks(&["Lazy", "dog"], "-"));
+/// assert!(blocklist.blocks(&["lazy", "hit"], "-"));
+/// assert!(blocklist.blocks(&["wash", "itch"], ""));
+/// assert!(!blocklist.blocks(&["wash", "itch"], "-"));
+/// ```
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Blocklist {
+    words: BTreeSet<String>,
+    pairs: BTreeSet<(String, String)>,
+}
+
+impl Blocklist {
+    /// An empty blocklist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bundled blocklist of profanity, and of pairs of words that read as
+    /// insults, such as `big pig`.
+    #[cfg(feature = "default-blocklist")]
+    pub fn builtin() -> Self {
+        Self::parse(include_str!("../words/blocklist.txt")).expect("bundled blocklist is valid")
+    }
+
+    /// Parse a blocklist with one entry per line: a word to block, or a pair
+    /// of words separated by whitespace. Blank lines, and anything after a
+    /// `#`, are ignored.
+    pub fn parse(text: &str) -> Result<Self, BlocklistError> {
+        let mut blocklist = Self::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(line, _)| line);
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => {}
+                [word] => blocklist.block_word(word),
+                [first, second] => blocklist.block_pair(first, second),
+                _ => return Err(BlocklistError::TooManyWords(number + 1)),
+            }
+        }
+        Ok(blocklist)
+    }
+
+    /// Block a word.
+    pub fn block_word(&mut self, word: &str) {
+        self.words.insert(word.to_lowercase());
+    }
+
+    /// Block `first` followed by `second`.
+    pub fn block_pair(&mut self, first: &str, second: &str) {
+        self.pairs.insert((first.to_lowercase(), second.to_lowercase()));
+    }
+
+    /// Block every word and pair in `other` too.
+    pub fn extend(&mut self, other: Blocklist) {
+        self.words.extend(other.words);
+        self.pairs.extend(other.pairs);
+    }
+
+    /// Whether nothing is blocked.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.pairs.is_empty()
+    }
+
+    /// Whether `word` is blocked.
+    pub fn blocks_word(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// Whether a name of `words` joined by `separator` is blocked.
+    pub fn blocks(&self, words: &[&str], separator: &str) -> bool {
+        let words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        words.iter().any(|word| self.words.contains(word))
+            || words.windows(2).any(|pair| {
+                self.pairs.contains(&(pair[0].clone(), pair[1].clone()))
+                    || self
+                        .spans(separator)
+                        .any(|(head, tail)| pair[0].ends_with(head) && pair[1].starts_with(tail))
+            })
+    }
+
+    /// Each way that a blocked word can span the boundary between two words
+    /// joined by `separator`: the end of the first word, and the start of the
+    /// second.
+    fn spans<'b>(&'b self, separator: &'b str) -> impl Iterator<Item = (&'b str, &'b str)> + 'b {
+        self.words.iter().flat_map(move |word| {
+            word.char_indices().skip(1).filter_map(move |(position, _)| {
+                let (head, rest) = word.split_at(position);
+                rest.strip_prefix(separator).filter(|tail| !tail.is_empty()).map(|tail| (head, tail))
+            })
+        })
+    }
+}
+
+/// The reason that [`Blocklist::parse`] could not parse a blocklist.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BlocklistError {
+    /// This line, counting from 1, has more than two words.
+    TooManyWords(usize),
+}
+
+impl fmt::Display for BlocklistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyWords(line) => write!(f, "line {line} has more than two words"),
+        }
+    }
+}
+
+impl core::error::Error for BlocklistError {}
+
+/// A view of [`Petnames`] that never generates a name that a [`Blocklist`]
+/// blocks.
+///
+/// Names are checked as joined by the separator given to [`Blocking::new`],
+/// so use [`namer`][`Self::namer`], which joins them the same way.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "default-rng")] {
+/// let petnames = petname::Petnames::new("lazy quick", "", "cat dog");
+/// let blocklist = petname::Blocklist::parse("lazy dog").unwrap();
+/// let blocking = petname::Blocking::new(&petnames, &blocklist, "-");
+/// assert_eq!(blocking.cardinality(2), 3);
+/// let name = blocking.namer(2).iter(&mut rand::rng()).next().unwrap();
+/// assert_ne!(name, "lazy-dog");
+/// # }
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct Blocking<'a, 'w> {
+    petnames: &'a Petnames<'w>,
+    separator: String,
+    /// Whether each adverb, adjective, and noun is blocked.
+    blocked: [Vec<bool>; 3],
+    /// The words that cannot follow each word: adverbs after adverbs,
+    /// adjectives after adverbs, and nouns after adjectives.
+    follows: [BTreeMap<usize, BTreeSet<usize>>; 3],
+}
+
+impl<'a, 'w> Blocking<'a, 'w> {
+    /// A view of `petnames` without names that `blocklist` blocks when joined
+    /// by `separator`.
+    pub fn new(petnames: &'a Petnames<'w>, blocklist: &Blocklist, separator: &str) -> Self {
+        let lower = |list: List| words(petnames, list).iter().map(|word| word.to_lowercase()).collect();
+        let (adverbs, adjectives, nouns): (Vec<String>, Vec<String>, Vec<String>) =
+            (lower(List::Adverb), lower(List::Adjective), lower(List::Noun));
+        let blocked = [&adverbs, &adjectives, &nouns]
+            .map(|words| words.iter().map(|word| blocklist.words.contains(word)).collect());
+        let follows =
+            [(&adverbs, &adverbs), (&adverbs, &adjectives), (&adjectives, &nouns)].map(|(from, to)| {
+                let mut follows: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+                let mut block = |first: &dyn Fn(&str) -> bool, second: &dyn Fn(&str) -> bool| {
+                    let seconds: Vec<usize> = (0..to.len()).filter(|&j| second(&to[j])).collect();
+                    if !seconds.is_empty() {
+                        for i in (0..from.len()).filter(|&i| first(&from[i])) {
+                            follows.entry(i).or_default().extend(&seconds);
+                        }
+                    }
+                };
+                for (first, second) in &blocklist.pairs {
+                    block(&|word| word == first, &|word| word == second);
+                }
+                for (head, tail) in blocklist.spans(separator) {
+                    block(&|word| word.ends_with(head), &|word| word.starts_with(tail));
+                }
+                follows
+            });
+        Self { petnames, separator: separator.to_string(), blocked, follows }
+    }
+
+    /// The underlying word lists.
+    pub fn petnames(&self) -> &'a Petnames<'w> {
+        self.petnames
+    }
+
+    /// The separator that names are checked as joined by.
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Calculate the number of names of `words` words that are not blocked.
+    ///
+    /// This can saturate. If the total possible combinations of words exceeds
+    /// `u128::MAX` then this will return `u128::MAX`.
+    pub fn cardinality(&self, words: u8) -> u128 {
+        self.ways(words).first().map_or(0, |ways| ways.iter().copied().fold(0, u128::saturating_add))
+    }
+
+    /// Create a [`Namer`] that generates names that are not blocked, joined
+    /// by this view's separator.
+    pub fn namer(&self, words: u8) -> Namer<'_, Self> {
+        Namer::new(self, words, &self.separator)
+    }
+
+    /// Whether the words at `digits`, from `lists`, make a blocked name.
+    fn blocks(&self, lists: &[List], digits: &[usize]) -> bool {
+        lists.iter().zip(digits).any(|(&list, &digit)| self.blocked[slot(list)][digit])
+            || lists.windows(2).zip(digits.windows(2)).any(|(lists, digits)| {
+                self.follows(lists[0], lists[1], digits[0])
+                    .is_some_and(|follows| follows.contains(&digits[1]))
+            })
+    }
+
+    /// The words in `next` that cannot follow the word at `digit` in `list`.
+    fn follows(&self, list: List, next: List, digit: usize) -> Option<&BTreeSet<usize>> {
+        self.all_follows(list, next)?.get(&digit)
+    }
+
+    /// The words in `next` that cannot follow each word in `list`.
+    fn all_follows(&self, list: List, next: List) -> Option<&BTreeMap<usize, BTreeSet<usize>>> {
+        match (list, next) {
+            (List::Adverb, List::Adverb) => Some(&self.follows[0]),
+            (List::Adverb, List::Adjective) => Some(&self.follows[1]),
+            (List::Adjective, List::Noun) => Some(&self.follows[2]),
+            _ => None,
+        }
+    }
+
+    /// For each position in a name of `words` words, the number of names
+    /// that are not blocked that continue from each word in that position.
+    fn ways(&self, words: u8) -> Vec<Vec<u128>> {
+        let lists: Vec<List> = Lists::new(words).collect();
+        let mut ways: Vec<Vec<u128>> = Vec::with_capacity(lists.len());
+        for (position, &list) in lists.iter().enumerate().rev() {
+            let blocked = &self.blocked[slot(list)];
+            let counts = match ways.last() {
+                None => blocked.iter().map(|&blocked| u128::from(!blocked)).collect(),
+                Some(next) => {
+                    let total = next.iter().copied().fold(0, u128::saturating_add);
+                    (0..blocked.len())
+                        .map(|digit| match (blocked[digit], self.follows(list, lists[position + 1], digit)) {
+                            (true, _) => 0,
+                            (false, None) => total,
+                            (false, Some(follows)) => follows
+                                .iter()
+                                .fold(total, |total, &following| total.saturating_sub(next[following])),
+                        })
+                        .collect()
+                }
+            };
+            ways.push(counts);
+        }
+        ways.reverse();
+        ways
+    }
+
+    /// The index of the name of the words at `digits`, from `lists`, among
+    /// those that are not blocked, given the [`ways`][`Self::ways`] for that
+    /// many words.
+    fn rank(&self, ways: &[Vec<u128>], lists: &[List], digits: &[usize]) -> Option<u128> {
+        if self.blocks(lists, digits) {
+            return None;
+        }
+        let mut index = 0u128;
+        for (position, &digit) in digits.iter().enumerate() {
+            let follows = position
+                .checked_sub(1)
+                .and_then(|previous| self.follows(lists[previous], lists[position], digits[previous]));
+            for other in (0..digit).filter(|other| !follows.is_some_and(|follows| follows.contains(other))) {
+                index = index.checked_add(ways[position][other])?;
+            }
+        }
+        Some(index)
+    }
+
+    fn push_into(&self, buf: &mut String, lists: &[List], digits: &[usize], separator: &str) {
+        for (position, (&list, &digit)) in lists.iter().zip(digits).enumerate() {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(words(self.petnames, list)[digit]);
+        }
+    }
+}
+
+impl Generator for Blocking<'_, '_> {
+    /// Names are drawn at random until one is not blocked. If that takes too
+    /// long, the name is instead chosen uniformly from those that are left,
+    /// if any. Unlike [`Petnames`], a name is never missing words: when a
+    /// list has no words, nothing is generated.
+    fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
+        let lists: Vec<List> = Lists::new(words).collect();
+        for _ in 0..ATTEMPTS {
+            let digits: Option<Vec<usize>> = lists
+                .iter()
+                .map(|&list| match words_len(self.petnames, list) {
+                    0 => None,
+                    len => Some(rng.random_range(0..len)),
+                })
+                .collect();
+            let Some(digits) = digits else { return };
+            if !self.blocks(&lists, &digits) {
+                self.push_into(buf, &lists, &digits, separator);
+                return;
+            }
+        }
+        let cardinality = self.cardinality(words);
+        if cardinality > 0 {
+            self.generate_at_into(buf, rng.random_range(0..cardinality), words, separator);
+        }
+    }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.petnames.word_ranges(name, words, separator)
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        self.petnames.word_finder(words, separator)
+    }
+}
+
+impl Indexed for Blocking<'_, '_> {
+    fn cardinality(&self, words: u8) -> u128 {
+        Blocking::cardinality(self, words)
+    }
+
+    /// Names are in the same order as for [`Petnames`], skipping those that
+    /// are blocked.
+    fn generate_at_into(&self, buf: &mut String, mut index: u128, words: u8, separator: &str) {
+        if index >= self.cardinality(words) {
+            return;
+        }
+        let lists: Vec<List> = Lists::new(words).collect();
+        let ways = self.ways(words);
+        let mut digits: Vec<usize> = Vec::with_capacity(lists.len());
+        for (position, counts) in ways.iter().enumerate() {
+            let follows = digits
+                .last()
+                .and_then(|&previous| self.follows(lists[position - 1], lists[position], previous));
+            // This cannot fail: the counts add up to the cardinality.
+            let Some(digit) = (0..counts.len())
+                .filter(|digit| !follows.is_some_and(|follows| follows.contains(digit)))
+                .find(|&digit| match index.checked_sub(counts[digit]) {
+                    Some(rest) => {
+                        index = rest;
+                        false
+                    }
+                    None => true,
+                })
+            else {
+                return;
+            };
+            digits.push(digit);
+        }
+        self.push_into(buf, &lists, &digits, separator);
+    }
+
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        let lists: Vec<List> = Lists::new(words).collect();
+        let ways = self.ways(words);
+        self.petnames
+            .split_words(name, words, separator, usize::MAX)
+            .into_iter()
+            .find_map(|digits| self.rank(&ways, &lists, &digits))
+    }
+
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        let lists: Vec<List> = Lists::new(words).collect();
+        let ways = self.ways(words);
+        let positions = self.petnames.positions();
+        names
+            .iter()
+            .map(|name| {
+                let mut indices: Vec<u128> = positions
+                    .split_words(name, words, separator, usize::MAX)
+                    .into_iter()
+                    .filter_map(|digits| self.rank(&ways, &lists, &digits))
+                    .collect();
+                indices.sort_unstable();
+                indices
+            })
+            .collect()
+    }
+}
+
+impl Petnames<'_> {
+    /// Remove words so that no name has a word or pair of words that
+    /// `blocklist` blocks when joined by `separator`: the blocked words, and
+    /// the second of each blocked pair of words. Returns the number of words
+    /// removed.
+    ///
+    /// This leaves out more names than [`Blocking`] does, which skips only
+    /// the names with a blocked pair, but keeps to plain word lists, as
+    /// needed where words are grouped, e.g. in [`Alliterations`].
+    ///
+    /// [`Alliterations`]: crate::Alliterations
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use petname::{Blocklist, Petnames};
+    /// let mut petnames = Petnames::new("big small", "", "pig wig");
+    /// let blocklist = Blocklist::parse("big pig").unwrap();
+    /// assert_eq!(petnames.remove_blocked(&blocklist, "-"), 1);
+    /// assert_eq!(petnames, Petnames::new("big small", "", "wig"));
+    /// ```
+    ///
+    pub fn remove_blocked(&mut self, blocklist: &Blocklist, separator: &str) -> usize {
+        let blocking = Blocking::new(self, blocklist, separator);
+        // The words that cannot follow some word are adverbs, adjectives, and
+        // nouns in turn, as the words themselves are blocked.
+        let [adverbs, adjectives, nouns] = [0, 1, 2].map(|slot| {
+            let blocked = &blocking.blocked[slot];
+            let following = blocking.follows[slot].values().flatten().copied();
+            following.chain((0..blocked.len()).filter(|&digit| blocked[digit])).collect::<BTreeSet<usize>>()
+        });
+        let retain = |words: &mut Words, removed: BTreeSet<usize>| {
+            let mut digits = 0..;
+            words.to_mut().retain(|_| digits.next().is_some_and(|digit| !removed.contains(&digit)));
+            removed.len()
+        };
+        retain(&mut self.adverbs, adverbs)
+            + retain(&mut self.adjectives, adjectives)
+            + retain(&mut self.nouns, nouns)
+    }
+}
+
+impl Lengths for Blocking<'_, '_> {
+    fn by_letters(&self, words: u8, max_letters: usize) -> Box<dyn ByLetters + '_> {
+        Box::new(ByLength::new(self, words, max_letters))
+    }
+}
+
+/// The names that a [`Blocking`] does not block, counted by length.
+///
+/// Within each length, the words in each position that some words cannot
+/// follow come first, then the rest, which all have the same number of ways
+/// to finish a name, so that a name can be found without counting the ways
+/// from every word.
+struct ByLength<'s, 'a, 'w> {
+    blocking: &'s Blocking<'a, 'w>,
+    lists: Vec<List>,
+    /// For each position, the words of each length that are not blocked:
+    /// those that some words cannot follow, and then the rest, in order.
+    words: Vec<Vec<(Vec<usize>, Vec<usize>)>>,
+    /// For each position, and one past the last, the number of ways to
+    /// finish a name from that position with each number of letters.
+    tails: Vec<Vec<u128>>,
+    /// For each position, the number of ways to finish a name from each word
+    /// there that some words cannot follow, or that cannot follow some word,
+    /// with each number of letters, counting its own.
+    ways: Vec<BTreeMap<usize, Vec<u128>>>,
+}
+
+impl<'s, 'a, 'w> ByLength<'s, 'a, 'w> {
+    fn new(blocking: &'s Blocking<'a, 'w>, words: u8, max_letters: usize) -> Self {
+        let lists: Vec<List> = Lists::new(words).collect();
+        // There are no names without words, as with `Petnames::cardinality`.
+        let mut last = vec![0; max_letters + 1];
+        last[0] = u128::from(!lists.is_empty());
+        let (mut tails, mut ways, mut grouped) = (vec![last], vec![BTreeMap::new()], Vec::new());
+        for (position, &list) in lists.iter().enumerate().rev() {
+            let next = lists.get(position + 1).and_then(|&next| blocking.all_follows(list, next));
+            let previous =
+                position.checked_sub(1).and_then(|previous| blocking.all_follows(lists[previous], list));
+            let length = |digit: usize| self::words(blocking.petnames, list)[digit].chars().count();
+
+            let mut groups = vec![(Vec::new(), Vec::new()); max_letters + 1];
+            for digit in
+                (0..words_len(blocking.petnames, list)).filter(|&digit| !blocking.blocked[slot(list)][digit])
+            {
+                if let Some((some, rest)) = groups.get_mut(length(digit)) {
+                    match next.is_some_and(|next| next.contains_key(&digit)) {
+                        true => some.push(digit),
+                        false => rest.push(digit),
+                    }
+                }
+            }
+
+            let (tail, after) = (&tails[0], &ways[0]);
+            let ways_from = |digit: usize| -> Vec<u128> {
+                let follows = next.and_then(|next| next.get(&digit));
+                (0..=max_letters)
+                    .map(|letters| match letters.checked_sub(length(digit)) {
+                        None => 0,
+                        Some(rest) => follows.into_iter().flatten().fold(tail[rest], |ways, following| {
+                            ways.saturating_sub(
+                                after.get(following).map_or(0, |after: &Vec<u128>| after[rest]),
+                            )
+                        }),
+                    })
+                    .collect()
+            };
+            let targets = previous.into_iter().flat_map(|previous| previous.values().flatten());
+            let here: BTreeMap<usize, Vec<u128>> = groups
+                .iter()
+                .flat_map(|(some, _)| some.iter().copied())
+                .chain(targets.copied().filter(|&digit| !blocking.blocked[slot(list)][digit]))
+                .map(|digit| (digit, ways_from(digit)))
+                .collect();
+
+            let tail = (0..=max_letters)
+                .map(|letters| {
+                    groups.iter().enumerate().take(letters + 1).fold(
+                        0u128,
+                        |total, (length, (some, rest))| {
+                            some.iter()
+                                .fold(total, |total, digit| total.saturating_add(here[digit][letters]))
+                                .saturating_add((rest.len() as u128).saturating_mul(tail[letters - length]))
+                        },
+                    )
+                })
+                .collect();
+            tails.insert(0, tail);
+            ways.insert(0, here);
+            grouped.insert(0, groups);
+        }
+        Self { blocking, lists, words: grouped, tails, ways }
+    }
+
+    /// Choose the word at `position` with `letters` letters left for the
+    /// rest of the name, given that it cannot be one of `excluded`, leaving
+    /// `index` among the ways to finish the name from there. Returns the
+    /// word and its length.
+    fn choose(
+        &self,
+        position: usize,
+        letters: usize,
+        excluded: Option<&BTreeSet<usize>>,
+        index: &mut u128,
+    ) -> Option<(usize, usize)> {
+        let excludes = |digit: &usize| excluded.is_some_and(|excluded| excluded.contains(digit));
+        for (length, (some, rest)) in self.words[position].iter().enumerate().take(letters + 1) {
+            for &digit in some.iter().filter(|digit| !excludes(digit)) {
+                let ways = self.ways[position][&digit][letters];
+                if *index < ways {
+                    return Some((digit, length));
+                }
+                *index -= ways;
+            }
+            // The rest all have the same number of ways to finish the name.
+            let ways = self.tails[position + 1][letters - length];
+            let skipped: Vec<usize> =
+                excluded.into_iter().flatten().filter_map(|digit| rest.binary_search(digit).ok()).collect();
+            let names = ((rest.len() - skipped.len()) as u128).saturating_mul(ways);
+            if *index < names {
+                let mut nth = (*index / ways) as usize;
+                for skip in skipped {
+                    if skip <= nth {
+                        nth += 1;
+                    }
+                }
+                *index %= ways;
+                return Some((rest[nth], length));
+            }
+            *index -= names;
+        }
+        None
+    }
+}
+
+impl ByLetters for ByLength<'_, '_, '_> {
+    fn cardinality(&self) -> &[u128] {
+        &self.tails[0]
+    }
+
+    fn generate_into(&self, buf: &mut String, mut index: u128, letters: usize, separator: &str) {
+        if index >= self.tails[0].get(letters).copied().unwrap_or(0) {
+            return;
+        }
+        let mut digits: Vec<usize> = Vec::with_capacity(self.lists.len());
+        let mut remaining = letters;
+        for position in 0..self.lists.len() {
+            let excluded = digits.last().and_then(|&previous| {
+                self.blocking.follows(self.lists[position - 1], self.lists[position], previous)
+            });
+            // This cannot fail: the ways from each word add up.
+            let Some((digit, length)) = self.choose(position, remaining, excluded, &mut index) else {
+                return;
+            };
+            digits.push(digit);
+            remaining -= length;
+        }
+        self.blocking.push_into(buf, &self.lists, &digits, separator);
+    }
+}
+
+/// The position of `list` in [`Blocking`]'s arrays.
+fn slot(list: List) -> usize {
+    match list {
+        List::Adverb => 0,
+        List::Adjective => 1,
+        List::Noun => 2,
+    }
+}
+
+fn words<'p, 'w>(petnames: &'p Petnames<'w>, list: List) -> &'p [&'w str] {
+    match list {
+        List::Adverb => &petnames.adverbs,
+        List::Adjective => &petnames.adjectives,
+        List::Noun => &petnames.nouns,
+    }
+}
+
+fn words_len(petnames: &Petnames, list: List) -> usize {
+    words(petnames, list).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+    use rand::SeedableRng;
+
+    use super::{Blocking, Blocklist, BlocklistError};
+    use crate::{Indexed, Lengths, Petnames};
+
+    #[test]
+    fn parse_reads_words_and_pairs() {
+        let blocklist = Blocklist::parse("# A comment.\nHit\n\n  lazy   DOG # Another.\n").unwrap();
+        assert!(blocklist.blocks_word("hit"));
+        assert!(blocklist.blocks(&["lazy", "dog"], "-"));
+        assert!(!blocklist.blocks(&["dog", "lazy"], "-"));
+        assert_eq!(Blocklist::parse("a\nb c d"), Err(BlocklistError::TooManyWords(2)));
+    }
+
+    #[test]
+    fn blocked_words_span_boundaries_only_with_the_separator() {
+        let blocklist = Blocklist::parse("hit").unwrap();
+        assert!(blocklist.blocks(&["wash", "itch"], ""));
+        assert!(!blocklist.blocks(&["wash", "itch"], "-"));
+        let blocklist = Blocklist::parse("h-it").unwrap();
+        assert!(blocklist.blocks(&["wash", "itch"], "-"));
+    }
+
+    #[test]
+    fn cardinality_counts_names_that_are_not_blocked() {
+        let petnames = Petnames::new("bad good", "very", "cat dog eel");
+        let blocklist = Blocklist::parse("eel\nbad dog\nvery good").unwrap();
+        let blocking = Blocking::new(&petnames, &blocklist, "-");
+        assert_eq!(blocking.cardinality(1), 2);
+        assert_eq!(blocking.cardinality(2), 3);
+        assert_eq!(blocking.cardinality(3), 1);
+        assert_eq!(blocking.cardinality(4), 1);
+    }
+
+    #[test]
+    fn name_at_and_index_of_are_inverses() {
+        let petnames = Petnames::new("bad good odd", "very so", "cat dog eel");
+        let blocklist = Blocklist::parse("so\nbad dog\nvery good\nddd").unwrap();
+        // Checked as if joined without a separator, e.g. "odddog".
+        let blocking = Blocking::new(&petnames, &blocklist, "");
+        for words in 1..=4 {
+            let names: BTreeSet<String> = (0..blocking.cardinality(words))
+                .map(|index| {
+                    let name = blocking.name_at(index, words, "-").unwrap();
+                    assert_eq!(blocking.index_of(&name, words, "-"), Some(index), "name was {name:?}");
+                    name
+                })
+                .collect();
+            let expected: BTreeSet<String> = (0..petnames.cardinality(words))
+                .filter_map(|index| petnames.name_at(index, words, "-"))
+                .filter(|name| !blocklist.blocks(&name.split('-').collect::<Vec<_>>(), ""))
+                .collect();
+            assert!(!expected.contains("odd-dog"));
+            assert_eq!(names, expected);
+        }
+    }
+
+    #[test]
+    fn by_letters_enumerates_names_that_are_not_blocked() {
+        let petnames = Petnames::new("bad good odd", "very so", "cat dog eel hippo");
+        let blocklist = Blocklist::parse("so\nbad dog\nvery good\nddd").unwrap();
+        let blocking = Blocking::new(&petnames, &blocklist, "");
+        for words in 0..=4 {
+            let by_letters = blocking.by_letters(words, 24);
+            let mut total = 0;
+            for letters in 0..=24 {
+                let cardinality = by_letters.cardinality()[letters];
+                let names: BTreeSet<String> = (0..=cardinality)
+                    .map(|index| {
+                        let mut buf = String::new();
+                        by_letters.generate_into(&mut buf, index, letters, "-");
+                        buf
+                    })
+                    .filter(|name| !name.is_empty())
+                    .collect();
+                let expected: BTreeSet<String> = (0..blocking.cardinality(words))
+                    .filter_map(|index| blocking.name_at(index, words, "-"))
+                    .filter(|name| name.chars().filter(|&c| c != '-').count() == letters)
+                    .collect();
+                assert_eq!(names, expected, "{words} words of {letters} letters");
+                assert_eq!(names.len() as u128, cardinality);
+                total += cardinality;
+            }
+            assert_eq!(total, blocking.cardinality(words));
+        }
+    }
+
+    #[test]
+    fn generate_falls_back_when_nearly_every_name_is_blocked() {
+        let petnames = Petnames::new("bad good", "", "cat dog eel");
+        let blocklist = Blocklist::parse("bad cat\nbad dog\nbad eel\ngood cat\ngood dog").unwrap();
+        let blocking = Blocking::new(&petnames, &blocklist, "-");
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names: BTreeSet<String> = blocking.namer(2).iter(&mut rng).take(50).collect();
+        assert_eq!(names, [String::from("good-eel")].into());
+    }
+}
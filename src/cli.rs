@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{ops::RangeInclusive, path::PathBuf};
 
 use clap::{builder::PossibleValue, Parser, Subcommand, ValueHint};
 
@@ -15,9 +15,33 @@ pub struct Cli {
     #[arg(short, long, value_name = "WORDS", default_value_t = 2, value_hint = ValueHint::Other)]
     pub words: u8,
 
-    /// Separator between words
-    #[arg(short, long, value_name = "SEP", default_value = "-", value_hint = ValueHint::Other)]
-    pub separator: String,
+    /// Use the fewest words that give each name at least this many bits of
+    /// entropy, instead of a fixed number of words
+    #[arg(long, value_name = "BITS", conflicts_with = "words", value_hint = ValueHint::Other)]
+    pub min_entropy: Option<f64>,
+
+    /// Separator between words [default: -, or the conventional separator for
+    /// --case]
+    #[arg(short, long, value_name = "SEP", value_hint = ValueHint::Other)]
+    pub separator: Option<String>,
+
+    /// Change the case of each word
+    ///
+    /// Not supported by encode or decode.
+    #[arg(long, value_name = "CASE")]
+    pub case: Option<Case>,
+
+    /// Append a random suffix of N characters to each name: digits:N,
+    /// alnum:N (digits and lower-case letters), or consonants:N (no vowels, so
+    /// never a word)
+    #[arg(
+        long,
+        value_name = "KIND:N",
+        value_parser = parse_suffix,
+        conflicts_with = "template",
+        value_hint = ValueHint::Other,
+    )]
+    pub suffix: Option<petname::Suffix>,
 
     /// Language to generate names in
     #[arg(long, visible_alias = "lang", value_name = "LANG", default_value_t)]
@@ -45,18 +69,154 @@ pub struct Cli {
     #[arg(long, conflicts_with = "count")]
     pub stream: bool,
 
+    /// Keep every pair of names at least this many edits apart – insertions,
+    /// deletions, or substitutions of a character – so that they are easy to
+    /// tell apart, e.g. not both calm-otter and calm-otters
+    #[arg(
+        long,
+        value_name = "EDITS",
+        conflicts_with_all = ["stream", "from_key", "keys_from_stdin"],
+        value_hint = ValueHint::Other,
+    )]
+    pub min_distance: Option<usize>,
+
+    /// Begin every name with a different word, e.g. not both brave-fox and
+    /// brave-otter
+    #[arg(long, conflicts_with_all = ["stream", "from_key", "keys_from_stdin"])]
+    pub different_first_words: bool,
+
     /// Maximum number of letters in each word; 0 for unlimited
     #[arg(short, long, value_name = "LETTERS", default_value_t = 0, value_hint = ValueHint::Other)]
     pub letters: usize,
 
-    /// Generate names where each word begins with the same letter
-    #[arg(short, long)]
-    pub alliterate: bool,
+    /// Minimum number of letters in each word, e.g. 3 to leave out ox and go
+    #[arg(long, value_name = "LETTERS", default_value_t = 0, value_hint = ValueHint::Other)]
+    pub min_letters: usize,
+
+    /// Maximum number of characters in each name, counting separators and
+    /// any suffix, e.g. 63 for a DNS label
+    ///
+    /// Names are chosen uniformly from among those that fit. Not supported with
+    /// --template, --distinct-words, or --prefer-common, nor by encode, decode,
+    /// or stats.
+    #[arg(
+        long,
+        value_name = "LENGTH",
+        conflicts_with_all = ["template", "distinct_words", "prefer_common"],
+        value_hint = ValueHint::Other,
+    )]
+    pub max_length: Option<usize>,
+
+    /// Generate names valid where they are to be used: dns-label, k8s-name,
+    /// filename, rust-ident, python-ident, or env-var
+    ///
+    /// Each profile sets the case, the separator, and the maximum length, if
+    /// any, and leaves out words with characters that are not allowed, as well
+    /// as words that are reserved on their own, like CON on Windows or type in
+    /// Rust. Not supported by encode or decode.
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["case", "separator", "template", "distinct_words", "prefer_common"],
+    )]
+    pub profile: Option<Profile>,
+
+    /// Generate names where each word begins with the same letter, or the
+    /// same sound with --alliterate=sound
+    ///
+    /// By sound, cat and kitten alliterate, as do knowing and nightingale, but
+    /// cheerful and cat do not; every vowel sound alliterates with every other.
+    #[arg(
+        short,
+        long,
+        value_name = "BY",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "letter",
+    )]
+    pub alliterate: Option<Alliterate>,
 
     /// Generate names where each word begins with the given letter
+    ///
+    /// The letter can be given in any case, with or without accents: -A É
+    /// chooses the same names as -A e. With --alliterate=sound, it stands for
+    /// the sound it usually spells, so -A c chooses names like cat-kitten.
     #[arg(short = 'A', long, value_name = "LETTER", value_hint = ValueHint::Other)]
     pub alliterate_with: Option<char>,
 
+    /// Generate names where every word rhymes, e.g. mellow-yellow-fellow
+    ///
+    /// Words rhyme when they are spelled alike from their last vowel sound,
+    /// or from the one before if the word ends weakly, as in -y, -er, or -ow.
+    #[arg(
+        long,
+        conflicts_with_all = ["alliterate", "alliterate_with", "ubuntu", "template", "distinct_words", "prefer_common"],
+    )]
+    pub rhyme: bool,
+
+    /// Generate names where every word has the same first two letters
+    /// (first2), the same last letter (last), or the same length (length)
+    #[arg(
+        long,
+        value_name = "KEY",
+        conflicts_with_all = [
+            "alliterate", "alliterate_with", "ubuntu", "rhyme", "template", "distinct_words", "prefer_common",
+        ],
+    )]
+    pub group_by: Option<GroupBy>,
+
+    /// With --alliterate, --rhyme, or --group-by, choose each letter, rhyme, or
+    /// group equally often, rather than each name
+    ///
+    /// By default every name is equally likely, so names beginning with common
+    /// letters are the most common. With this, a letter with few words, like
+    /// x, is chosen as often as one with many, like s. Names derived from
+    /// keys, and names limited by --max-length, are not affected.
+    #[arg(long)]
+    pub sample_by_group: bool,
+
+    /// Leave out words that sound like another word, e.g. keep only one of
+    /// knight and night, so that names are easy to tell apart when read aloud
+    #[arg(long)]
+    pub phonetic_safe: bool,
+
+    /// Generate names shaped by a pattern instead of by --words
+    ///
+    /// The pattern is literal text with slots in braces: {adverb}, {adjective},
+    /// and {noun} for a word from that list; {digits:N}, {hex:N}, and
+    /// {letters:N} for N random characters, where N is from 1 (the default) to
+    /// 16. Use {{ and }} for literal braces. Word slots need literal text
+    /// between them. For example, "{adjective}-{noun}-{digits:3}".
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        conflicts_with_all = ["words", "min_entropy", "separator", "alliterate", "alliterate_with", "ubuntu", "distinct_words"],
+        value_hint = ValueHint::Other,
+    )]
+    pub template: Option<String>,
+
+    /// Choose common words more often than obscure ones
+    ///
+    /// No word frequencies are bundled: built-in words are weighted by the
+    /// lists they are in, 4 for the small list, 2 for the medium list, and 1
+    /// for the rest, so this does nothing with --lists=small. Words in the
+    /// files given by --dir may carry a weight after a colon, e.g. fox:2.5; a
+    /// word without one has a weight of 1, and weights are ignored without
+    /// this option. --min-entropy and stats count the effective entropy of
+    /// these names, which is less than if every word were equally likely.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "alliterate", "alliterate_with", "ubuntu", "template", "distinct_words",
+            "from_key", "keys_from_stdin", "exclude_file",
+        ],
+    )]
+    pub prefer_common: bool,
+
+    /// Never use the same word twice in one name
+    #[arg(long, conflicts_with_all = ["alliterate", "alliterate_with", "ubuntu"])]
+    pub distinct_words: bool,
+
     // For compatibility with upstream.
     /// Alias for compatibility with upstream; prefer --alliterate instead
     #[arg(short, long, conflicts_with = "alliterate", conflicts_with = "alliterate_with")]
@@ -72,10 +232,108 @@ pub struct Cli {
     #[arg(long, value_name = "SEED", value_hint = ValueHint::Other)]
     pub seed: Option<u64>,
 
+    /// Derive a name from this key instead of choosing one at random
+    ///
+    /// The same key always produces the same name, in this and future versions
+    /// of rust-petname, as long as the word lists and other options are the
+    /// same. Useful for giving commit hashes, UUIDs, and so on a memorable
+    /// alias.
+    #[arg(long, value_name = "KEY", conflicts_with_all = ["count", "stream", "seed"], value_hint = ValueHint::Other)]
+    pub from_key: Option<String>,
+
+    /// Read keys from standard input, one per line, and derive a name for each
+    ///
+    /// See --from-key for details.
+    #[arg(long, conflicts_with_all = ["from_key", "count", "stream", "seed"])]
+    pub keys_from_stdin: bool,
+
+    /// Never generate any of the names in this file, one per line; use - to
+    /// read them from standard input
+    ///
+    /// Useful for avoiding names that are already in use. Names are compared
+    /// as they are printed, with --case, --profile, and --suffix applied.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["from_key", "keys_from_stdin"], value_hint = ValueHint::FilePath)]
+    pub exclude_file: Option<PathBuf>,
+
+    /// Never generate names with the words or pairs of words in this file,
+    /// as well as those in the built-in blocklist
+    ///
+    /// Each line is a word, or two words that must never be next to each other
+    /// in that order; anything after a # is ignored. A blocked word must not be
+    /// formed across two words either, e.g. when --separator is empty.
+    ///
+    /// Blocked pairs, and words formed across two words, are avoided in random
+    /// names of adverbs, adjectives, and nouns, with or without --max-length,
+    /// and with --alliterate, --rhyme, or --group-by, by leaving out the second
+    /// word of each pair. They are not avoided with --distinct-words,
+    /// --template, or --prefer-common, nor in names derived from keys or by
+    /// encode, which must never change; unless words are grouped, a warning
+    /// is printed for each of those that is blocked.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub blocklist: Option<PathBuf>,
+
+    /// Do not use the built-in blocklist of offensive words and pairs of words
+    #[arg(long)]
+    pub no_default_blocklist: bool,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
 
+impl Cli {
+    /// The separator between words: as given, or else the conventional one
+    /// for --case, or else `-`.
+    pub fn separator(&self) -> &str {
+        match (&self.separator, self.style()) {
+            (Some(separator), _) => separator,
+            (None, Some(style)) => style.separator(),
+            (None, None) => "-",
+        }
+    }
+
+    /// The style of each word: from --case, or else from --profile.
+    pub fn style(&self) -> Option<petname::Style> {
+        self.case.map(Case::style).or(self.profile.map(|profile| profile.0.style()))
+    }
+
+    /// The maximum length of each name: the least of --max-length and the
+    /// maximum length of --profile, if either is given.
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_length.into_iter().chain(self.profile.and_then(|profile| profile.0.max_len())).min()
+    }
+
+    /// The letter given with --alliterate-with, folded as alliterations are,
+    /// or the sound it stands for with --alliterate=sound.
+    pub fn alliteration_letter(&self) -> Option<char> {
+        let letter = petname::Folding::default().fold(self.alliterate_with?);
+        match self.alliterate {
+            Some(Alliterate::Sound) => {
+                Some(petname::initial_sound(letter.encode_utf8(&mut [0; 4])).unwrap_or(letter))
+            }
+            Some(Alliterate::Letter) | None => Some(letter),
+        }
+    }
+
+    /// How --alliterate, --rhyme, and --group-by choose names at random.
+    pub fn sampling(&self) -> petname::Sampling {
+        if self.sample_by_group {
+            petname::Sampling::ByGroup
+        } else {
+            petname::Sampling::Uniform
+        }
+    }
+
+    /// The range of letters – characters, not bytes – allowed in each word,
+    /// from --min-letters and --letters; `None` if any number is allowed.
+    pub fn letters_range(&self) -> Option<RangeInclusive<usize>> {
+        match (self.min_letters, self.letters) {
+            (0, 0) => None,
+            (min, 0) => Some(min..=usize::MAX),
+            (min, max) => Some(min..=max),
+        }
+    }
+}
+
 /// Subcommands. These are additive: with no subcommand, `petname` generates
 /// names exactly as before.
 #[derive(Subcommand)]
@@ -86,6 +344,62 @@ pub enum Command {
         #[arg(value_name = "SHELL")]
         shell: clap_complete::Shell,
     },
+
+    /// Check that names could have been generated with the given options
+    ///
+    /// Each name is checked against the word lists selected by --lists, --dir,
+    /// and --letters, with the words separated by --separator. With --alliterate
+    /// or --alliterate-with, each name must alliterate too. Exits with a non-zero
+    /// status if any name is not valid.
+    Check {
+        /// Require exactly this number of words in each name
+        #[arg(short, long, value_name = "WORDS", value_hint = ValueHint::Other)]
+        words: Option<u8>,
+
+        /// The names to check
+        #[arg(value_name = "NAME", required = true, value_hint = ValueHint::Other)]
+        names: Vec<String>,
+    },
+
+    /// Encode bytes from standard input as names
+    ///
+    /// Reads hexadecimal from standard input – ignoring whitespace, colons, and
+    /// a leading 0x – and prints names, one per line, that `petname decode` can
+    /// turn back into the same bytes. The names embed a checksum. Decode with
+    /// the same word lists, --words, and --separator as used to encode.
+    Encode {
+        /// Read raw bytes instead of hexadecimal
+        #[arg(long)]
+        raw: bool,
+    },
+
+    /// Decode names from standard input back into bytes
+    ///
+    /// Reads names, one per line, as printed by `petname encode`, and prints
+    /// the bytes that they encode in hexadecimal.
+    Decode {
+        /// Write raw bytes instead of hexadecimal
+        #[arg(long)]
+        raw: bool,
+    },
+
+    /// Describe the names that could be generated with the given options
+    ///
+    /// Applies the word lists, --dir, --letters, and alliteration options, then
+    /// prints the number of words in each list, and how many words
+    /// --phonetic-safe removed, if given; the number of names, their
+    /// entropy, and how many names can be chosen before a 50% or 1% chance of
+    /// a collision, for each number of words; and, when alliterating, the size
+    /// of each list for each initial letter.
+    Stats {
+        /// Describe names of up to this number of words
+        #[arg(long, value_name = "WORDS", default_value_t = 4, value_hint = ValueHint::Other)]
+        max_words: u8,
+
+        /// Print statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -122,6 +436,205 @@ impl clap::ValueEnum for WordList {
     }
 }
 
+/// What the words of an alliterative name have in common at their start.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alliterate {
+    /// The same first letter.
+    #[default]
+    Letter,
+    /// The same first sound; see [`petname::Alliterations::phonetic`].
+    Sound,
+}
+
+impl std::fmt::Display for Alliterate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Letter => write!(f, "letter"),
+            Self::Sound => write!(f, "sound"),
+        }
+    }
+}
+
+impl clap::ValueEnum for Alliterate {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Letter, Self::Sound]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Letter => PossibleValue::new("letter"),
+            Self::Sound => PossibleValue::new("sound"),
+        })
+    }
+}
+
+/// What the words of a name have in common, for --group-by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    /// The same first two letters.
+    First2,
+    /// The same last letter.
+    Last,
+    /// The same number of letters.
+    Length,
+}
+
+impl GroupBy {
+    /// The key that groups `word` with others.
+    pub fn key(self, word: &str) -> GroupKey {
+        match self {
+            Self::First2 => GroupKey::Letters(word.chars().take(2).collect()),
+            Self::Last => GroupKey::Letter(word.chars().last().unwrap_or_default()),
+            Self::Length => GroupKey::Length(word.chars().count()),
+        }
+    }
+
+    /// Split `petnames` into groups of words with the same key.
+    pub fn group(self, petnames: petname::Petnames<'_>) -> petname::Alliterations<'_, GroupKey> {
+        petname::Alliterations::group_by(petnames, |word| self.key(word))
+    }
+}
+
+/// What the words in each group have in common, for --group-by.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GroupKey {
+    Letters(String),
+    Letter(char),
+    Length(usize),
+}
+
+impl std::fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::First2 => write!(f, "first2"),
+            Self::Last => write!(f, "last"),
+            Self::Length => write!(f, "length"),
+        }
+    }
+}
+
+impl clap::ValueEnum for GroupBy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::First2, Self::Last, Self::Length]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::First2 => PossibleValue::new("first2"),
+            Self::Last => PossibleValue::new("last"),
+            Self::Length => PossibleValue::new("length"),
+        })
+    }
+}
+
+/// The case of each word in a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    Lower,
+    Upper,
+    Title,
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+    ScreamingSnake,
+}
+
+impl Case {
+    pub fn style(self) -> petname::Style {
+        match self {
+            Self::Lower => petname::Style::Lower,
+            Self::Upper => petname::Style::Upper,
+            Self::Title => petname::Style::Title,
+            Self::Camel => petname::Style::Camel,
+            Self::Pascal => petname::Style::Pascal,
+            Self::Snake => petname::Style::Snake,
+            Self::Kebab => petname::Style::Kebab,
+            Self::ScreamingSnake => petname::Style::ScreamingSnake,
+        }
+    }
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lower => write!(f, "lower"),
+            Self::Upper => write!(f, "upper"),
+            Self::Title => write!(f, "title"),
+            Self::Camel => write!(f, "camel"),
+            Self::Pascal => write!(f, "pascal"),
+            Self::Snake => write!(f, "snake"),
+            Self::Kebab => write!(f, "kebab"),
+            Self::ScreamingSnake => write!(f, "screaming-snake"),
+        }
+    }
+}
+
+impl clap::ValueEnum for Case {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Lower,
+            Self::Upper,
+            Self::Title,
+            Self::Camel,
+            Self::Pascal,
+            Self::Snake,
+            Self::Kebab,
+            Self::ScreamingSnake,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            Self::Lower => "lower",
+            Self::Upper => "upper",
+            Self::Title => "title",
+            Self::Camel => "camel",
+            Self::Pascal => "pascal",
+            Self::Snake => "snake",
+            Self::Kebab => "kebab",
+            Self::ScreamingSnake => "screaming-snake",
+        }))
+    }
+}
+
+/// Where names are to be used; see [`petname::Profile`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Profile(pub petname::Profile);
+
+impl clap::ValueEnum for Profile {
+    fn value_variants<'a>() -> &'a [Self] {
+        const PROFILES: [Profile; 6] = [
+            Profile(petname::Profile::DnsLabel),
+            Profile(petname::Profile::K8sName),
+            Profile(petname::Profile::Filename),
+            Profile(petname::Profile::RustIdent),
+            Profile(petname::Profile::PythonIdent),
+            Profile(petname::Profile::EnvVar),
+        ];
+        &PROFILES
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.0.name()))
+    }
+}
+
+/// Parse a suffix given as `KIND:N`, e.g. `digits:4`.
+fn parse_suffix(value: &str) -> Result<petname::Suffix, String> {
+    let (kind, length) = value.split_once(':').ok_or("expected KIND:N, e.g. digits:4")?;
+    let alphabet = match kind {
+        "digits" => petname::Alphabet::Digits,
+        "alnum" => petname::Alphabet::Alphanumeric,
+        "consonants" => petname::Alphabet::Consonants,
+        _ => return Err(format!("unknown kind {kind:?}; expected digits, alnum, or consonants")),
+    };
+    match length.parse() {
+        Ok(length) if length > 0 => Ok(petname::Suffix::new(alphabet, length)),
+        _ => Err(format!("invalid length {length:?}; expected a number from 1 to 255")),
+    }
+}
+
 /// The language used to generate names.
 ///
 /// The `Turkish` variant is only available when the `lang-turkish` feature is
@@ -194,10 +707,10 @@ mod compatibility {
     /// -s|--separator string used to separate name words, default is '-',
     #[test]
     fn compat_separator() {
-        assert_eq!(Cli::parse_from(["petname"]).separator, "-");
-        assert_eq!(Cli::parse_from(["petname", "-s", ":"]).separator, ":");
-        assert_eq!(Cli::parse_from(["petname", "--separator", "|"]).separator, "|");
-        assert_eq!(Cli::parse_from(["petname", "--separator=."]).separator, ".");
+        assert_eq!(Cli::parse_from(["petname"]).separator(), "-");
+        assert_eq!(Cli::parse_from(["petname", "-s", ":"]).separator(), ":");
+        assert_eq!(Cli::parse_from(["petname", "--separator", "|"]).separator(), "|");
+        assert_eq!(Cli::parse_from(["petname", "--separator=."]).separator(), ".");
     }
 
     /// -c|--complexity [0, 1, 2]; 0 = easy words, 1 = standard words, 2 =
@@ -0,0 +1,280 @@
+//! Names that a [`Namer`] never generates, as given to
+//! [`Namer::with_excluded`].
+
+use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
+
+use crate::{Generator, Indexed, Namer};
+
+/// How many names to draw at random, looking for one that is not excluded,
+/// before instead taking the first that is not excluded in a random order of
+/// every name.
+const ATTEMPTS: usize = 16;
+
+/// The names that a [`Namer`] never generates.
+pub(crate) struct Excluded<G: ?Sized> {
+    names: BTreeSet<String>,
+    /// Choose a name that is not excluded, if there are any, without drawing
+    /// at random; this needs an [`Indexed`] generator.
+    remaining: fn(&Namer<'_, G>, &mut dyn rand::Rng) -> Option<String>,
+}
+
+impl<'a, G: Indexed + ?Sized> Namer<'a, G> {
+    /// Never generate any of the `excluded` names, e.g. names already in use.
+    ///
+    /// Names are compared as this namer generates them, with its style and
+    /// suffix, if any, so with [`Style::Title`][`crate::Style::Title`], say,
+    /// exclude `Brave-Otter` rather than `brave-otter`. Calling this again
+    /// excludes more names. Like the style, this applies to everything except
+    /// [`name_for_key`][`Self::name_for_key`], [`encode`][`Self::encode`], and
+    /// [`decode`][`Self::decode`].
+    ///
+    /// [`cardinality`][`Self::cardinality`] counts only the names that are
+    /// not excluded. When every name is excluded, nothing is generated, so
+    /// [`iter`][`Self::iter`] ends rather than looping forever. To be counted,
+    /// an excluded name is looked up in the word lists as it is and, with a
+    /// style, in lower case; one whose words are in the lists in neither form,
+    /// e.g. `FAIR-CAT` in upper case from a list with `Fair`, is never
+    /// generated, but is not subtracted from the cardinality.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-rng")] {
+    /// use petname::Style;
+    /// let petnames = petname::Petnames::new("fair", "", "cat dog");
+    /// let namer = petnames.namer(2, "-").with_style(Style::Title).with_excluded(["Fair-Cat"]);
+    /// assert_eq!(namer.cardinality(), 1);
+    /// let names: Vec<String> = namer.iter(&mut rand::rng()).take(3).collect();
+    /// assert_eq!(names, ["Fair-Dog", "Fair-Dog", "Fair-Dog"]);
+    ///
+    /// let namer = petnames.namer(2, "-").with_excluded(["fair-cat", "fair-dog"]);
+    /// assert_eq!(namer.cardinality(), 0);
+    /// assert_eq!(namer.iter(&mut rand::rng()).next(), None);
+    /// # }
+    /// ```
+    ///
+    pub fn with_excluded<I>(mut self, excluded: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut names = self.excluded.take().map_or_else(BTreeSet::new, |excluded| excluded.names);
+        names.extend(excluded.into_iter().map(Into::into));
+        Self { excluded: Some(Excluded { names, remaining: first_remaining }), ..self }
+    }
+
+    /// The number of names, counted as [`cardinality`][`Self::cardinality`]
+    /// counts them, that are excluded.
+    pub(crate) fn excluded_names(&self) -> u128 {
+        let Some(excluded) = &self.excluded else {
+            return 0;
+        };
+        let unstyled: Vec<String> = excluded.names.iter().flat_map(|name| self.unstyled(name)).collect();
+        let names: Vec<&str> = unstyled.iter().map(String::as_str).collect();
+        // A name is at more than one index when it splits into words in more
+        // than one way.
+        let indices = self.generator.indices_of(&names, self.words, self.separator);
+        indices.iter().map(|indices| indices.len() as u128).fold(0, u128::saturating_add)
+    }
+
+    /// The names, without style or suffix, that this namer generates as
+    /// `name`, if it generates it at all.
+    fn unstyled(&self, name: &str) -> Vec<String> {
+        // Each suffix is at an index of its own, so only the words can repeat.
+        let words = match self.suffix.filter(|suffix| suffix.length() > 0) {
+            None => name,
+            Some(suffix) => {
+                let Some((start, _)) = name.char_indices().nth_back(usize::from(suffix.length()) - 1) else {
+                    return Vec::new();
+                };
+                match name[..start].strip_suffix(self.separator) {
+                    Some(words) if suffix.contains(&name[start..]) => words,
+                    _ => return Vec::new(),
+                }
+            }
+        };
+        let mut unstyled = vec![String::from(words)];
+        if self.style.is_some() {
+            let mut lower = String::new();
+            self.generator.lowercase_into(&mut lower, words);
+            if lower != words {
+                unstyled.push(lower);
+            }
+        }
+        let fits = |name: &str| match self.by_letters() {
+            None => true,
+            Some((counts, _)) => name
+                .chars()
+                .count()
+                .checked_sub(self.separators())
+                .is_some_and(|letters| letters < counts.len()),
+        };
+        let styled = |name: &str| {
+            let mut buf = String::new();
+            self.generate_styled(&mut buf, |buf| buf.push_str(name));
+            buf
+        };
+        unstyled.retain(|name| fits(name) && styled(name) == words);
+        unstyled
+    }
+}
+
+impl<G: Generator + ?Sized> Namer<'_, G> {
+    /// Whether `name` is excluded.
+    pub(crate) fn is_excluded(&self, name: &str) -> bool {
+        self.excluded.as_ref().is_some_and(|excluded| excluded.names.contains(name))
+    }
+
+    /// Push a name that is not excluded onto `buf`, if there are any.
+    ///
+    /// Names are drawn at random until one is not excluded. If that takes too
+    /// long, most names are probably excluded, so the name is instead the
+    /// first that is not excluded in a random order of every name.
+    pub(crate) fn generate_remaining_into(
+        &self,
+        buf: &mut String,
+        rng: &mut dyn rand::Rng,
+        excluded: &Excluded<G>,
+    ) {
+        let mut name = String::new();
+        for _ in 0..ATTEMPTS {
+            self.generate_any_into(&mut name, rng);
+            if name.is_empty() {
+                return;
+            } else if !excluded.names.contains(&name) {
+                buf.push_str(&name);
+                return;
+            }
+            name.clear();
+        }
+        if let Some(name) = (excluded.remaining)(self, rng) {
+            buf.push_str(&name);
+        }
+    }
+}
+
+/// The first name that is not excluded in a random order of every name, which
+/// is as likely to be any one of them as any other.
+fn first_remaining<G: Indexed + ?Sized>(namer: &Namer<'_, G>, rng: &mut dyn rand::Rng) -> Option<String> {
+    namer.unique_iter(rng).next()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeSet, string::String};
+
+    use rand::SeedableRng;
+
+    use crate::{Alliterations, Alphabet, Petnames, Style, Suffix};
+
+    #[test]
+    fn cardinality_ignores_names_that_cannot_be_generated() {
+        let petnames = Petnames::new("able bold", "very", "ant bee");
+        let excluded = ["able-ant", "able-ant", "very-able-ant", "cold-ant", "ant"];
+        assert_eq!(petnames.namer(1, "-").with_excluded(excluded).cardinality(), 1);
+        assert_eq!(petnames.namer(2, "-").with_excluded(excluded).cardinality(), 3);
+        assert_eq!(petnames.namer(3, "-").with_excluded(excluded).cardinality(), 3);
+        // Names joined by a different separator are not the same names.
+        assert_eq!(petnames.namer(2, "_").with_excluded(excluded).cardinality(), 4);
+    }
+
+    #[test]
+    fn cardinality_resolves_names_for_the_number_of_words() {
+        // "very-able-ant" is "very" + "able-ant", and "very" + "able" + "ant".
+        let petnames = Petnames::new("very able", "very", "ant able-ant");
+        let excluded = ["very-able-ant"];
+        assert_eq!(petnames.namer(2, "-").with_excluded(excluded).cardinality(), petnames.cardinality(2) - 1);
+        assert_eq!(petnames.namer(3, "-").with_excluded(excluded).cardinality(), petnames.cardinality(3) - 1);
+    }
+
+    #[test]
+    fn cardinality_counts_names_split_another_way_at_each_index() {
+        // "x-ray-fish" is both "x-ray" + "fish" and "x" + "ray-fish".
+        let petnames = Petnames::new("x-ray x", "", "fish ray-fish");
+        let namer = petnames.namer(2, "-").with_excluded(["x-ray-fish", "x-fish"]);
+        assert_eq!(namer.cardinality(), 1);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names: BTreeSet<String> = namer.iter(&mut rng).take(200).collect();
+        assert_eq!(names, [String::from("x-ray-ray-fish")].into());
+        let namer = namer.with_excluded(["x-ray-ray-fish"]);
+        assert_eq!(namer.cardinality(), 0);
+        assert_eq!(namer.iter(&mut rng).next(), None);
+    }
+
+    #[test]
+    fn generate_never_produces_excluded_names() {
+        let petnames = Petnames::new("able bold cold", "", "ant bee cow");
+        let excluded = ["able-ant", "able-bee", "bold-bee", "cold-ant", "cold-cow"];
+        let namer = petnames.namer(2, "-").with_excluded(excluded);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names: BTreeSet<String> = namer.iter(&mut rng).take(200).collect();
+        assert_eq!(names, ["able-cow", "bold-ant", "bold-cow", "cold-bee"].map(String::from).into());
+    }
+
+    #[test]
+    fn names_are_compared_with_style() {
+        let petnames = Petnames::new("brave", "", "fox-cub otter");
+        let namer = petnames.namer(2, "-").with_style(Style::Title);
+        assert_eq!(namer.with_excluded(["brave-otter"]).cardinality(), 2);
+        let namer = petnames.namer(2, "-").with_style(Style::Title).with_excluded(["Brave-Otter"]);
+        assert_eq!(namer.cardinality(), 1);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names: BTreeSet<String> = namer.iter(&mut rng).take(200).collect();
+        // Words are found by the generator, so "fox-cub" is one word.
+        assert_eq!(names, [String::from("Brave-Fox-cub")].into());
+        // Without a separator, words are found in the word lists.
+        let namer = petnames.namer(2, "").with_style(Style::Pascal).with_excluded(["BraveFox-cub"]);
+        assert_eq!(namer.cardinality(), 1);
+        assert_eq!(namer.iter(&mut rng).next().as_deref(), Some("BraveOtter"));
+    }
+
+    #[test]
+    fn names_are_compared_with_suffix() {
+        let petnames = Petnames::new("fair", "", "cat");
+        let excluded = (0..9).map(|digit| alloc::format!("fair-cat-{digit}"));
+        let namer =
+            petnames.namer(2, "-").with_suffix(Suffix::new(Alphabet::Digits, 1)).with_excluded(excluded);
+        assert_eq!(namer.cardinality(), 1);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names: BTreeSet<String> = namer.iter(&mut rng).take(20).collect();
+        assert_eq!(names, [String::from("fair-cat-9")].into());
+        // Without its suffix, a name is not one that this namer generates.
+        let namer = petnames.namer(2, "-").with_suffix(Suffix::new(Alphabet::Digits, 1));
+        assert_eq!(namer.with_excluded(["fair-cat", "fair-cat-10"]).cardinality(), 10);
+    }
+
+    #[test]
+    fn names_are_counted_only_if_they_fit() {
+        let petnames = Petnames::new("big tiny", "", "ant hippopotamus");
+        let namer = petnames.namer(2, "-").with_max_len(8).with_excluded(["big-ant", "tiny-hippopotamus"]);
+        assert_eq!(namer.cardinality(), 1);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let names: BTreeSet<String> = namer.iter(&mut rng).take(20).collect();
+        assert_eq!(names, [String::from("tiny-ant")].into());
+    }
+
+    #[test]
+    fn generate_falls_back_when_nearly_every_name_is_excluded() {
+        let petnames = Petnames::new("able bold", "very quite", "ant bee cow");
+        let namer = petnames.namer(3, "-");
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        let mut unique = namer.unique_iter(&mut rng);
+        let last = unique.next().unwrap();
+        let namer = petnames.namer(3, "-").with_excluded(unique);
+        assert_eq!(namer.cardinality(), 1);
+        let names: BTreeSet<String> = namer.iter(&mut rng).take(20).collect();
+        assert_eq!(names, [last].into());
+    }
+
+    #[test]
+    fn generate_produces_nothing_when_every_name_is_excluded() {
+        let petnames = Petnames::new("able bold", "", "ant bee");
+        let mut alliterations: Alliterations = petnames.into();
+        alliterations.retain(|_, group| group.cardinality(2) > 0);
+        let namer = alliterations.namer(2, "-").with_excluded(["able-ant", "bold-bee"]);
+        assert_eq!(namer.cardinality(), 0);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        assert_eq!(namer.iter(&mut rng).next(), None);
+        assert_eq!(namer.unique_iter(&mut rng).next(), None);
+    }
+}
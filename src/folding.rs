@@ -0,0 +1,155 @@
+//! Folding the letters that words begin with, so that `Émile`, `emerald`, and
+//! `Ember` alliterate.
+//!
+//! This uses a small table of its own rather than Unicode's character data.
+//! Only the first letter of a word is folded, and a combining mark after it – as in `E` and
+//! U+0301 COMBINING ACUTE ACCENT, the decomposed form of `É` – is ignored,
+//! except where Turkish counts it as part of a distinct letter. Accents are
+//! removed from the Latin letters in Latin-1 and Latin Extended-A; letters in
+//! other scripts are lowercased and otherwise kept as they are.
+
+/// How to fold the letter that a word begins with, for alliteration.
+///
+/// Accents are removed only from the letters in Latin-1 and Latin Extended-A,
+/// so `é` is `e` but `ǎ` and `ạ` are kept as they are. A decomposed letter,
+/// like `e` followed by a combining acute accent, folds as `é` does, since a
+/// combining mark after the first letter is ignored.
+///
+/// Folding is used by [`Alliterations::by_first_letter`], which groups the
+/// words of a [`Petnames`], whatever their language. The Turkish `Petnames`
+/// in `lang::turkish` cannot alliterate yet.
+///
+/// [`Alliterations::by_first_letter`]: crate::Alliterations::by_first_letter
+/// [`Petnames`]: crate::Petnames
+///
+/// # Examples
+///
+/// ```rust
+/// use petname::Folding;
+/// assert_eq!(Folding::Latin.first_letter("Émile"), Some('e'));
+/// assert_eq!(Folding::Latin.first_letter("Ember"), Some('e'));
+/// assert_eq!(Folding::Latin.first_letter("çiçek"), Some('c'));
+/// assert_eq!(Folding::Turkish.first_letter("Çiçek"), Some('ç'));
+/// assert_eq!(Folding::Turkish.first_letter("Irmak"), Some('ı'));
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Folding {
+    /// Ignore case and accents: `É`, `é`, `E`, and `e` are all `e`, and
+    /// `İ`, `ı`, `I`, and `i` are all `i`.
+    #[default]
+    Latin,
+    /// Ignore case by Turkish rules, in which `I` is the capital of `ı` and
+    /// `İ` is the capital of `i`. The letters `ç`, `ğ`, `ı`, `ö`, `ş`, and `ü`
+    /// are letters of the Turkish alphabet in their own right, so they are
+    /// kept apart from `c`, `g`, `i`, `o`, `s`, and `u`; other accents, like
+    /// the circumflex in `kâğıt`, are ignored.
+    Turkish,
+}
+
+impl Folding {
+    /// The letter that `word` begins with, folded; `None` if `word` is empty.
+    pub fn first_letter(self, word: &str) -> Option<char> {
+        let mut chars = word.chars();
+        let letter = chars.next()?;
+        Some(match (self, letter, chars.next()) {
+            // Decomposed forms of the distinct Turkish letters.
+            (Self::Turkish, 'c' | 'C', Some('\u{327}')) => 'ç',
+            (Self::Turkish, 's' | 'S', Some('\u{327}')) => 'ş',
+            (Self::Turkish, 'g' | 'G', Some('\u{306}')) => 'ğ',
+            (Self::Turkish, 'o' | 'O', Some('\u{308}')) => 'ö',
+            (Self::Turkish, 'u' | 'U', Some('\u{308}')) => 'ü',
+            (Self::Turkish, 'I', Some('\u{307}')) => 'i',
+            (folding, letter, _) => folding.fold(letter),
+        })
+    }
+
+    /// Fold a single `letter`, e.g. one given to alliterate with.
+    pub fn fold(self, letter: char) -> char {
+        match (self, letter) {
+            (Self::Turkish, 'I') => 'ı',
+            (Self::Turkish, 'İ') => 'i',
+            (Self::Turkish, 'Ç' | 'ç') => 'ç',
+            (Self::Turkish, 'Ğ' | 'ğ') => 'ğ',
+            (Self::Turkish, 'ı') => 'ı',
+            (Self::Turkish, 'Ö' | 'ö') => 'ö',
+            (Self::Turkish, 'Ş' | 'ş') => 'ş',
+            (Self::Turkish, 'Ü' | 'ü') => 'ü',
+            // Lowercasing İ gives i followed by a combining dot above.
+            (_, letter) => without_accent(letter.to_lowercase().next().unwrap_or(letter)),
+        }
+    }
+}
+
+/// The lower-case Latin `letter` without its accent, or `letter` itself.
+fn without_accent(letter: char) -> char {
+    match letter {
+        'à'..='å' | 'ā' | 'ă' | 'ą' | 'æ' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ð' | 'ď' | 'đ' => 'd',
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' | 'œ' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ß' | 'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        letter => letter,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::Folding;
+
+    #[test]
+    fn latin_ignores_case_and_accents() {
+        for word in ["Émile", "E\u{301}mile", "emerald", "Ember", "ēon", "ęe"] {
+            assert_eq!(Folding::Latin.first_letter(word), Some('e'), "{word}");
+        }
+        for word in ["İstanbul", "Irmak", "ılık", "île"] {
+            assert_eq!(Folding::Latin.first_letter(word), Some('i'), "{word}");
+        }
+        assert_eq!(Folding::Latin.first_letter("Ørsted"), Some('o'));
+        assert_eq!(Folding::Latin.first_letter("Ωmega"), Some('ω'));
+        assert_eq!(Folding::Latin.first_letter(""), None);
+    }
+
+    #[test]
+    fn turkish_keeps_turkish_letters_apart() {
+        for (words, letter) in [
+            (["çiçek", "Çiçek", "c\u{327}ic\u{327}ek"], 'ç'),
+            (["ceviz", "Ceviz", "ceylan"], 'c'),
+            (["şeker", "Şeker", "s\u{327}eker"], 'ş'),
+            (["ılık", "Irmak", "ırmak"], 'ı'),
+            (["İstanbul", "I\u{307}stanbul", "incir"], 'i'),
+            (["ördek", "Ördek", "o\u{308}rdek"], 'ö'),
+            (["kâğıt", "Kedi", "kuzu"], 'k'),
+            (["âlem", "Ali", "arı"], 'a'),
+        ] {
+            for word in words {
+                assert_eq!(Folding::Turkish.first_letter(word), Some(letter), "{word}");
+            }
+        }
+    }
+
+    #[test]
+    fn fold_matches_first_letter() {
+        for folding in [Folding::Latin, Folding::Turkish] {
+            for letter in ['É', 'e', 'I', 'İ', 'ı', 'Ç', 'ş', 'Ü', 'x'] {
+                assert_eq!(folding.first_letter(&letter.to_string()), Some(folding.fold(letter)));
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+//! Names made from any number of named word lists, in any order.
+
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{fmt, ops::Range};
+
+use rand::seq::IndexedRandom;
+
+use crate::{
+    log2, mixed_radix, mixed_radix_index, positions, splits, style, Generator, Indexed, Lookup, Namer, Slots,
+};
+use crate::{Petnames, WordFinder, Words};
+
+/// A [`Generator`] of names made from named word lists, one word per _slot_.
+///
+/// The slots are given as list names separated by ASCII whitespace, e.g.
+/// `"color animal"` for names like `red-fox`. A name of `n` words uses the last `n`
+/// slots. One slot can be marked with a trailing `*` to repeat it to make
+/// longer names; without one, there are no names with more words than slots.
+///
+/// [`Petnames`] is the preset `"adverb* adjective noun"`, and converts into a
+/// [`Grammar`] with [`Grammar::from`]. The [`english!`][`crate::english!`]
+/// macro can embed other grammars at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "default-rng")] {
+/// let grammar = petname::Grammar::new("color animal")
+///     .unwrap()
+///     .with_words("color", "red green")
+///     .with_words("animal", "fox owl yak");
+/// assert_eq!(grammar.cardinality(2), 6);
+/// assert_eq!(grammar.cardinality(3), 0);
+/// let name = grammar.namer(2, "-").iter(&mut rand::rng()).next().unwrap();
+/// assert!(name.starts_with("red-") || name.starts_with("green-"));
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grammar<'a> {
+    lists: BTreeMap<Cow<'a, str>, Words<'a>>,
+    slots: Vec<String>,
+    /// The position of the slot that repeats, if any.
+    repeat: Option<usize>,
+}
+
+impl<'a> Grammar<'a> {
+    /// Parse `slots`, list names separated by ASCII whitespace, into a grammar
+    /// with no word lists yet.
+    pub fn new(slots: &str) -> Result<Self, GrammarError> {
+        check(slots)?;
+        let (mut names, mut repeat, mut rest) = (Vec::new(), None, slots);
+        while let Some((name, repeats, after)) = next_slot(rest) {
+            if repeats {
+                repeat = Some(names.len());
+            }
+            names.push(name.into());
+            rest = after;
+        }
+        Ok(Self { lists: BTreeMap::new(), slots: names, repeat })
+    }
+
+    /// Panic unless [`new`][`Self::new`] would accept `slots`. In a const
+    /// context this fails to compile instead; the [`english!`] macro uses it
+    /// to check slots.
+    ///
+    /// ```rust,compile_fail
+    /// const _: () = petname::Grammar::check_slots("color* animal*");
+    /// ```
+    ///
+    /// [`english!`]: crate::english!
+    #[doc(hidden)]
+    pub const fn check_slots(slots: &str) {
+        match check(slots) {
+            Ok(()) => {}
+            Err(Invalid::NoSlots) => panic!("no slots"),
+            Err(Invalid::Unnamed) => panic!("a slot has no list name"),
+            Err(Invalid::ManyRepeats(_)) => panic!("only one slot can repeat"),
+        }
+    }
+
+    /// Add, or replace, the word list called `name`.
+    pub fn with_list(mut self, name: impl Into<Cow<'a, str>>, words: impl Into<Words<'a>>) -> Self {
+        self.lists.insert(name.into(), words.into());
+        self
+    }
+
+    /// Add, or replace, the word list called `name`, extracting the words
+    /// from `words` by splitting on whitespace.
+    pub fn with_words(self, name: impl Into<Cow<'a, str>>, words: &'a str) -> Self {
+        self.with_list(name, words.split_whitespace().collect::<Vec<_>>())
+    }
+
+    /// The word list called `name`, if there is one.
+    pub fn list(&self, name: &str) -> Option<&[&'a str]> {
+        self.lists.get(name).map(|words| &words[..])
+    }
+
+    /// Keep words matching a predicate, in every list.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&str) -> bool,
+    {
+        for words in self.lists.values_mut() {
+            words.to_mut().retain(|word| predicate(word));
+        }
+    }
+
+    /// Calculate the number of distinct names of `words` words.
+    ///
+    /// This is zero if a slot has no list, or if there are more words than
+    /// slots and no slot repeats. This can saturate. If the total possible
+    /// combinations of words exceeds `u128::MAX` then this will return
+    /// `u128::MAX`.
+    pub fn cardinality(&self, words: u8) -> u128 {
+        match self.radices(words) {
+            Some(radices) if !radices.is_empty() => {
+                radices.into_iter().map(|radix| radix as u128).fold(1, u128::saturating_mul)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Create a [`Namer`] that generates names from this grammar.
+    pub fn namer<'b>(&'b self, words: u8, separator: &'b str) -> Namer<'b, Self> {
+        Namer::new(self, words, separator)
+    }
+
+    /// The slots used for a name of `words` words, if there are any.
+    fn slots(&self, words: u8) -> Option<Vec<&str>> {
+        Some(Slots::new(&self.slots, self.repeat, words)?.map(String::as_str).collect())
+    }
+
+    /// The length of the list used for each of `words` words.
+    fn radices(&self, words: u8) -> Option<Vec<usize>> {
+        let slots = self.slots(words)?;
+        Some(slots.into_iter().map(|slot| self.list(slot).map_or(0, <[_]>::len)).collect())
+    }
+
+    /// Split `name` into `words` words from these lists, if it can be. Each is
+    /// the position of each word in its list.
+    fn split(&self, name: &str, words: u8, separator: &str) -> Option<Vec<usize>> {
+        let slots = self.slots(words).filter(|slots| !slots.is_empty())?;
+        let finders: Vec<_> = slots
+            .iter()
+            .map(|slot| {
+                let list = self.list(slot);
+                move |word: &str| list?.iter().position(|candidate| *candidate == word)
+            })
+            .collect();
+        let lookups: Vec<Lookup> = finders.iter().map(|finder| finder as Lookup).collect();
+        splits(name, separator, &lookups, 1).pop()
+    }
+
+    /// The position of each word in the list for each slot of a name of
+    /// `words` words, for looking up the words of many names.
+    fn positions(&self, words: u8) -> Option<Vec<BTreeMap<&'a str, usize>>> {
+        let slots = self.slots(words).filter(|slots| !slots.is_empty())?;
+        Some(slots.iter().map(|slot| self.list(slot).map(positions).unwrap_or_default()).collect())
+    }
+
+    /// The words of a name of `words` words, as ranges of bytes in that name,
+    /// given the position of each word in its list.
+    fn joined_ranges(&self, words: u8, digits: Vec<usize>, separator: &str) -> Option<Vec<Range<usize>>> {
+        let slots = self.slots(words)?;
+        let lengths =
+            slots.iter().zip(digits).map(|(slot, digit)| self.list(slot).map_or(0, |list| list[digit].len()));
+        Some(style::joined_ranges(lengths, separator))
+    }
+}
+
+/// Find up to `limit` ways to split `name` into words, looking each up in
+/// `positions` in turn.
+fn split_in(
+    name: &str,
+    separator: &str,
+    positions: &[BTreeMap<&str, usize>],
+    limit: usize,
+) -> Vec<Vec<usize>> {
+    let finders: Vec<_> =
+        positions.iter().map(|positions| move |word: &str| positions.get(word).copied()).collect();
+    let lookups: Vec<Lookup> = finders.iter().map(|finder| finder as Lookup).collect();
+    splits(name, separator, &lookups, limit)
+}
+
+impl<'a> From<Petnames<'a>> for Grammar<'a> {
+    /// The grammar `"adverb* adjective noun"`, with the same word lists.
+    fn from(petnames: Petnames<'a>) -> Self {
+        Self::new("adverb* adjective noun")
+            .expect("valid slots")
+            .with_list("adverb", petnames.adverbs)
+            .with_list("adjective", petnames.adjectives)
+            .with_list("noun", petnames.nouns)
+    }
+}
+
+impl Generator for Grammar<'_> {
+    /// Slots whose lists are missing or empty are skipped, so a name _may_
+    /// contain fewer words than requested.
+    fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
+        let chosen = self.slots(words).unwrap_or_default().into_iter().filter_map(|slot| {
+            let list = self.list(slot)?;
+            list.choose(rng).copied()
+        });
+        for (position, word) in chosen.enumerate() {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(word);
+        }
+    }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.split(name, words, separator)
+            .and_then(|digits| self.joined_ranges(words, digits, separator))
+            .unwrap_or_else(|| style::split_ranges(name, separator))
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        let positions = self.positions(words).unwrap_or_default();
+        Box::new(move |name| {
+            split_in(name, separator, &positions, 1)
+                .pop()
+                .and_then(|digits| self.joined_ranges(words, digits, separator))
+                .unwrap_or_else(|| style::split_ranges(name, separator))
+        })
+    }
+}
+
+impl Indexed for Grammar<'_> {
+    fn cardinality(&self, words: u8) -> u128 {
+        Grammar::cardinality(self, words)
+    }
+
+    /// The sum of the entropy of each word, which does not saturate.
+    fn entropy_bits(&self, words: u8) -> f64 {
+        match (self.cardinality(words), self.radices(words)) {
+            (0, _) | (_, None) => 0.0,
+            (_, Some(radices)) => radices.into_iter().map(|radix| log2(radix as f64)).sum(),
+        }
+    }
+
+    fn generate_at_into(&self, buf: &mut String, index: u128, words: u8, separator: &str) {
+        let (Some(slots), Some(radices)) = (self.slots(words), self.radices(words)) else {
+            return;
+        };
+        if index >= self.cardinality(words) {
+            return;
+        }
+        for (position, (slot, digit)) in slots.into_iter().zip(mixed_radix(index, &radices)).enumerate() {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(self.lists[slot][digit]);
+        }
+    }
+
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        let digits = self.split(name, words, separator)?;
+        mixed_radix_index(&digits, &self.radices(words)?)
+    }
+
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        let (Some(positions), Some(radices)) = (self.positions(words), self.radices(words)) else {
+            return vec![Vec::new(); names.len()];
+        };
+        names
+            .iter()
+            .map(|name| {
+                let mut indices: Vec<u128> = split_in(name, separator, &positions, usize::MAX)
+                    .into_iter()
+                    .filter_map(|digits| mixed_radix_index(&digits, &radices))
+                    .collect();
+                indices.sort_unstable();
+                indices
+            })
+            .collect()
+    }
+}
+
+/// Find the first slot in `slots`: its list name, whether it repeats, and the
+/// rest of `slots` after it.
+///
+/// This and [`check`] are `const fn`s so that the [`english!`] macro can check
+/// slots at compile time, with [`Grammar::check_slots`], rather than parse
+/// them again.
+///
+/// [`english!`]: crate::english!
+const fn next_slot(slots: &str) -> Option<(&str, bool, &str)> {
+    let slots = slots.trim_ascii_start();
+    if slots.is_empty() {
+        return None;
+    }
+    let mut end = 0;
+    while end < slots.len() && !slots.as_bytes()[end].is_ascii_whitespace() {
+        end += 1;
+    }
+    let (slot, rest) = slots.split_at(end);
+    match slot.as_bytes() {
+        [.., b'*'] => Some((slot.split_at(slot.len() - 1).0, true, rest)),
+        _ => Some((slot, false, rest)),
+    }
+}
+
+/// Check that `slots` are valid for [`Grammar::new`].
+const fn check(slots: &str) -> Result<(), Invalid<'_>> {
+    let (mut rest, mut any, mut repeated) = (slots, false, false);
+    while let Some((name, repeats, after)) = next_slot(rest) {
+        if repeats && repeated {
+            return Err(Invalid::ManyRepeats(name));
+        } else if name.is_empty() {
+            return Err(Invalid::Unnamed);
+        }
+        (rest, any, repeated) = (after, true, repeated || repeats);
+    }
+    if any {
+        Ok(())
+    } else {
+        Err(Invalid::NoSlots)
+    }
+}
+
+/// Why [`check`] rejected some slots; a [`GrammarError`] that can be made in a
+/// const context.
+enum Invalid<'s> {
+    NoSlots,
+    Unnamed,
+    ManyRepeats(&'s str),
+}
+
+impl From<Invalid<'_>> for GrammarError {
+    fn from(invalid: Invalid<'_>) -> Self {
+        match invalid {
+            Invalid::NoSlots => Self::NoSlots,
+            Invalid::Unnamed => Self::Unnamed,
+            Invalid::ManyRepeats(name) => Self::ManyRepeats(name.into()),
+        }
+    }
+}
+
+/// The reason that [`Grammar::new`] could not parse slots.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GrammarError {
+    /// There are no slots.
+    NoSlots,
+    /// A slot is a `*` on its own, with no list name.
+    Unnamed,
+    /// More than one slot repeats; this is the second.
+    ManyRepeats(String),
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSlots => write!(f, "no slots"),
+            Self::Unnamed => write!(f, "a slot has no list name"),
+            Self::ManyRepeats(name) => write!(f, "only one slot can repeat, but {name} does too"),
+        }
+    }
+}
+
+impl core::error::Error for GrammarError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::{Grammar, GrammarError};
+
+    #[test]
+    fn new_parses_slots() {
+        let grammar = Grammar::new(" verb preposition*  place ").unwrap();
+        assert_eq!(grammar.slots, ["verb", "preposition", "place"].map(String::from));
+        assert_eq!(grammar.repeat, Some(1));
+        assert_eq!(Grammar::new("  "), Err(GrammarError::NoSlots));
+        assert_eq!(Grammar::new("a * b"), Err(GrammarError::Unnamed));
+        assert_eq!(Grammar::new("a* b*"), Err(GrammarError::ManyRepeats("b".into())));
+    }
+
+    #[test]
+    fn slots_drop_leading_slots_and_repeat() {
+        let grammar = Grammar::new("a b* c").unwrap();
+        assert_eq!(grammar.slots(0), Some(alloc::vec![]));
+        assert_eq!(grammar.slots(2), Some(alloc::vec!["b", "c"]));
+        assert_eq!(grammar.slots(5), Some(alloc::vec!["a", "b", "b", "b", "c"]));
+        let grammar = Grammar::new("a b c").unwrap();
+        assert_eq!(grammar.slots(4), None);
+    }
+
+    #[test]
+    fn missing_lists_have_no_names() {
+        let grammar = Grammar::new("color animal").unwrap().with_words("animal", "fox");
+        assert_eq!(grammar.cardinality(1), 1);
+        assert_eq!(grammar.cardinality(2), 0);
+    }
+}
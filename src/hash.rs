@@ -0,0 +1,29 @@
+//! Stable hashing for deriving names from keys.
+//!
+//! The hash used here is part of the contract of [`Indexed::name_for_key`]:
+//! changing it would change the name derived for every key, so it must not
+//! change.
+//!
+//! [`Indexed::name_for_key`]: crate::Indexed::name_for_key
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e_07bb0142_62b82175_6295c58d;
+const FNV_PRIME: u128 = 0x00000000_01000000_00000000_0000013b;
+
+/// The 128-bit [FNV-1a] hash of `bytes`.
+///
+/// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+pub(crate) fn fnv1a_128(bytes: &[u8]) -> u128 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| (hash ^ u128::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fnv1a_128;
+
+    #[test]
+    fn fnv1a_128_matches_reference_values() {
+        assert_eq!(fnv1a_128(b""), 0x6c62272e_07bb0142_62b82175_6295c58d);
+        assert_eq!(fnv1a_128(b"a"), 0xd228cb69_6f1a8caf_78912b70_4e4a8964);
+        assert_eq!(fnv1a_128(b"foobar"), 0x343e1662_793c64bf_6f0d3597_ba446f18);
+    }
+}
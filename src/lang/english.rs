@@ -1,10 +1,23 @@
 //! English petname generator.
 
-use alloc::{borrow::Cow, string::String};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    ops::{Range, RangeBounds},
+};
 
-use rand::seq::IndexedRandom;
+use rand::seq::{IndexedRandom, IteratorRandom};
 
-use crate::{Generator, List, Lists, Namer, Words};
+use crate::{
+    log2, max_words, mixed_radix, mixed_radix_index, positions, splits, style, Generator, Indexed, List,
+    Lists, Lookup, Namer, WordFinder, Words,
+};
 
 /// Word lists and the logic to combine them into English _petnames_.
 ///
@@ -89,6 +102,22 @@ impl<'a> Petnames<'a> {
         self.nouns.to_mut().retain(|word| predicate(word));
     }
 
+    /// Keep words whose number of letters – characters, not bytes – is in
+    /// the given range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use petname::lang::english::Petnames;
+    /// let mut petnames = Petnames::new("big enormous", "", "ox ant gnu elephant");
+    /// petnames.retain_letters(3..=5);
+    /// assert_eq!(petnames, Petnames::new("big", "", "ant gnu"));
+    /// ```
+    ///
+    pub fn retain_letters<R: RangeBounds<usize>>(&mut self, letters: R) {
+        self.retain(|word| letters.contains(&word.chars().count()));
+    }
+
     /// Calculate the cardinality of this [`Petnames`].
     ///
     /// If this is low, names may be repeated by the generator with a higher
@@ -121,10 +150,281 @@ impl<'a> Petnames<'a> {
     ///     .expect("no names");
     /// ```
     pub fn namer<'b>(&'b self, words: u8, separator: &'b str) -> Namer<'b, Self> {
-        Namer { generator: self, words, separator }
+        Namer::new(self, words, separator)
+    }
+
+    /// A view of these word lists that never uses the same word twice in one
+    /// name; see [`Distinct`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use petname::lang::english::Petnames;
+    /// let petnames = Petnames::new("gold", "", "gold silver");
+    /// assert_eq!(petnames.cardinality(2), 2);
+    /// assert_eq!(petnames.distinct().cardinality(2), 1);
+    /// ```
+    pub fn distinct(&self) -> Distinct<'_, 'a> {
+        Distinct::new(self)
+    }
+
+    /// Parse a petname into its words.
+    ///
+    /// This is the inverse of generating a name: it finds which words were
+    /// taken from which list, and the position of each in its list. The number
+    /// of words is inferred from the name itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use petname::lang::english::{ParseError, Petnames};
+    /// let petnames = Petnames::new("apt", "deftly", "swiftlet");
+    /// let parsed = petnames.parse("deftly-apt-swiftlet", "-").unwrap();
+    /// assert_eq!(parsed.adverbs[0].word, "deftly");
+    /// assert_eq!(parsed.adjective.unwrap().word, "apt");
+    /// assert_eq!(parsed.noun.word, "swiftlet");
+    /// assert_eq!(petnames.parse("deftly-apt-sparrow", "-"), Err(ParseError::UnknownWord("sparrow".into())));
+    /// ```
+    ///
+    pub fn parse(&self, name: &str, separator: &str) -> Result<ParsedName<'a>, ParseError> {
+        if name.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        match self.split_name(name, separator, 2).as_slice() {
+            [(words, digits)] => {
+                let mut parsed =
+                    ParsedName { adverbs: Vec::new(), adjective: None, noun: ParsedWord::default() };
+                for (list, &index) in Lists::new(*words).zip(digits) {
+                    match list {
+                        List::Adverb => parsed.adverbs.push(ParsedWord { word: self.adverbs[index], index }),
+                        List::Adjective => {
+                            parsed.adjective = Some(ParsedWord { word: self.adjectives[index], index })
+                        }
+                        List::Noun => parsed.noun = ParsedWord { word: self.nouns[index], index },
+                    }
+                }
+                Ok(parsed)
+            }
+            [] => {
+                // Only blame a particular word if words cannot themselves
+                // contain the separator; otherwise we cannot be sure where
+                // each word begins and ends.
+                let lists = [&self.adverbs, &self.adjectives, &self.nouns];
+                let known = |word: &str| lists.iter().any(|list| list.contains(&word));
+                let unambiguous = !separator.is_empty()
+                    && !lists.iter().any(|list| list.iter().any(|word| word.contains(separator)));
+                match name.split(separator).find(|word| !known(word)) {
+                    Some(word) if unambiguous => Err(ParseError::UnknownWord(word.into())),
+                    _ => Err(ParseError::Unrecognised),
+                }
+            }
+            _ => Err(ParseError::Ambiguous),
+        }
+    }
+
+    /// Find up to `limit` ways to split `name` into words from these lists,
+    /// trying fewer words first. Each is the number of words and the position
+    /// of each word in its list.
+    pub(crate) fn split_name(&self, name: &str, separator: &str, limit: usize) -> Vec<(u8, Vec<usize>)> {
+        let mut found = Vec::new();
+        for words in 1..=max_words(name, separator) {
+            if found.len() >= limit {
+                break;
+            }
+            found.extend(
+                self.split_words(name, words, separator, limit - found.len())
+                    .into_iter()
+                    .map(|digits| (words, digits)),
+            );
+        }
+        found
+    }
+
+    /// Find up to `limit` ways to split `name` into exactly `words` words from
+    /// these lists. Each is the position of each word in its list.
+    pub(crate) fn split_words(
+        &self,
+        name: &str,
+        words: u8,
+        separator: &str,
+        limit: usize,
+    ) -> Vec<Vec<usize>> {
+        let adverb = |word: &str| self.adverbs.iter().position(|candidate| *candidate == word);
+        let adjective = |word: &str| self.adjectives.iter().position(|candidate| *candidate == word);
+        let noun = |word: &str| self.nouns.iter().position(|candidate| *candidate == word);
+        split_words(name, words, separator, limit, [&adverb, &adjective, &noun])
+    }
+
+    /// The position of each word in each list, for splitting many names into
+    /// words.
+    pub(crate) fn positions(&self) -> Positions<'_> {
+        Positions {
+            adverbs: positions(&self.adverbs),
+            adjectives: positions(&self.adjectives),
+            nouns: positions(&self.nouns),
+        }
+    }
+
+    /// Find the words of `name`, a name of `words` words from these lists, as
+    /// ranges of bytes in `name`. Words are looked up in `positions`, if
+    /// given, rather than by searching each list.
+    pub(crate) fn find_word_ranges(
+        &self,
+        name: &str,
+        words: u8,
+        separator: &str,
+        positions: Option<&Positions>,
+    ) -> Option<Vec<Range<usize>>> {
+        // With only the separators between words, the name splits only one
+        // way, so there is no need to look up its words.
+        if !separator.is_empty() && name.matches(separator).count() + 1 == usize::from(words) {
+            return Some(style::split_ranges(name, separator));
+        }
+        let digits = match positions {
+            None => self.split_words(name, words, separator, 1),
+            Some(positions) => positions.split_words(name, words, separator, 1),
+        }
+        .pop()?;
+        let lengths = Lists::new(words).zip(digits).map(|(list, digit)| match list {
+            List::Adverb => self.adverbs[digit].len(),
+            List::Adjective => self.adjectives[digit].len(),
+            List::Noun => self.nouns[digit].len(),
+        });
+        Some(style::joined_ranges(lengths, separator))
+    }
+
+    /// Prepare to find the words of many names, as
+    /// [`find_word_ranges`][`Self::find_word_ranges`] does for one. The lists
+    /// are prepared for lookup only if names might need their words looked
+    /// up: when the separator is empty, or is in a word.
+    pub(crate) fn word_range_finder<'s>(&'s self, words: u8, separator: &'s str) -> RangeFinder<'s> {
+        let lists = [&self.adverbs, &self.adjectives, &self.nouns];
+        let positions = (separator.is_empty()
+            || lists.iter().any(|list| list.iter().any(|word| word.contains(separator))))
+        .then(|| self.positions());
+        Box::new(move |name| self.find_word_ranges(name, words, separator, positions.as_ref()))
     }
+
+    /// The length of the list used for each of `words` words.
+    pub(crate) fn radices(&self, words: u8) -> Vec<usize> {
+        Lists::new(words)
+            .map(|list| match list {
+                List::Adverb => self.adverbs.len(),
+                List::Adjective => self.adjectives.len(),
+                List::Noun => self.nouns.len(),
+            })
+            .collect()
+    }
+}
+
+/// Finds the words of a name from some [`Petnames`], if it is one, as made by
+/// [`Petnames::word_range_finder`].
+pub(crate) type RangeFinder<'s> = Box<dyn Fn(&str) -> Option<Vec<Range<usize>>> + 's>;
+
+/// The position of each word in each list of some [`Petnames`], as made by
+/// [`Petnames::positions`].
+pub(crate) struct Positions<'w> {
+    adverbs: BTreeMap<&'w str, usize>,
+    adjectives: BTreeMap<&'w str, usize>,
+    nouns: BTreeMap<&'w str, usize>,
+}
+
+impl Positions<'_> {
+    /// Find up to `limit` ways to split `name` into exactly `words` words, as
+    /// [`Petnames::split_words`] does, but without searching each list.
+    pub(crate) fn split_words(
+        &self,
+        name: &str,
+        words: u8,
+        separator: &str,
+        limit: usize,
+    ) -> Vec<Vec<usize>> {
+        let adverb = |word: &str| self.adverbs.get(word).copied();
+        let adjective = |word: &str| self.adjectives.get(word).copied();
+        let noun = |word: &str| self.nouns.get(word).copied();
+        split_words(name, words, separator, limit, [&adverb, &adjective, &noun])
+    }
+}
+
+/// Find up to `limit` ways to split `name` into exactly `words` words, looking
+/// up adverbs, adjectives, and nouns with each of `lookups` in turn.
+fn split_words(
+    name: &str,
+    words: u8,
+    separator: &str,
+    limit: usize,
+    lookups: [Lookup; 3],
+) -> Vec<Vec<usize>> {
+    let [adverb, adjective, noun] = lookups;
+    let lookups: Vec<Lookup> = Lists::new(words)
+        .map(|list| match list {
+            List::Adverb => adverb,
+            List::Adjective => adjective,
+            List::Noun => noun,
+        })
+        .collect();
+    if lookups.is_empty() {
+        return Vec::new();
+    }
+    splits(name, separator, &lookups, limit)
+}
+
+/// A petname split into its words by [`Petnames::parse`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParsedName<'a> {
+    /// The adverbs, in order; there are none in names of fewer than 3 words.
+    pub adverbs: Vec<ParsedWord<'a>>,
+    /// The adjective; there is none in a name of 1 word.
+    pub adjective: Option<ParsedWord<'a>>,
+    /// The noun.
+    pub noun: ParsedWord<'a>,
 }
 
+impl ParsedName<'_> {
+    /// The number of words in the name.
+    pub fn words(&self) -> usize {
+        self.adverbs.len() + usize::from(self.adjective.is_some()) + 1
+    }
+}
+
+/// A word in a [`ParsedName`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ParsedWord<'a> {
+    /// The word itself.
+    pub word: &'a str,
+    /// The position of the word in its word list.
+    pub index: usize,
+}
+
+/// The reason that [`Petnames::parse`] could not parse a name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The name is empty.
+    Empty,
+    /// The name contains a word that is in none of the word lists.
+    UnknownWord(String),
+    /// The name cannot be split into words that these word lists could have
+    /// produced, e.g. it has a noun where the adjective should be, or it is an
+    /// adjective on its own.
+    Unrecognised,
+    /// The name can be split into words in more than one way, e.g. because a
+    /// word contains the separator.
+    Ambiguous,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "name is empty"),
+            Self::UnknownWord(word) => write!(f, "unknown word: {word}"),
+            Self::Unrecognised => write!(f, "not a name that these word lists can produce"),
+            Self::Ambiguous => write!(f, "name can be split into words in more than one way"),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
 impl Generator for Petnames<'_> {
     fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
         for list in Lists::new(words) {
@@ -149,6 +449,345 @@ impl Generator for Petnames<'_> {
             };
         }
     }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.find_word_ranges(name, words, separator, None)
+            .unwrap_or_else(|| style::split_ranges(name, separator))
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        let find = self.word_range_finder(words, separator);
+        Box::new(move |name| find(name).unwrap_or_else(|| style::split_ranges(name, separator)))
+    }
+}
+
+impl Indexed for Petnames<'_> {
+    fn cardinality(&self, words: u8) -> u128 {
+        Petnames::cardinality(self, words)
+    }
+
+    /// The sum of the entropy of each word, which does not saturate.
+    fn entropy_bits(&self, words: u8) -> f64 {
+        match self.cardinality(words) {
+            0 => 0.0,
+            _ => self.radices(words).into_iter().map(|radix| log2(radix as f64)).sum(),
+        }
+    }
+
+    fn generate_at_into(&self, buf: &mut String, index: u128, words: u8, separator: &str) {
+        if index >= self.cardinality(words) {
+            return;
+        }
+        for (position, (list, digit)) in
+            Lists::new(words).zip(mixed_radix(index, &self.radices(words))).enumerate()
+        {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(match list {
+                List::Adverb => self.adverbs[digit],
+                List::Adjective => self.adjectives[digit],
+                List::Noun => self.nouns[digit],
+            });
+        }
+    }
+
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        let digits = self.split_words(name, words, separator, 1).pop()?;
+        mixed_radix_index(&digits, &self.radices(words))
+    }
+
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        let radices = self.radices(words);
+        let positions = self.positions();
+        names
+            .iter()
+            .map(|name| {
+                let mut indices: Vec<u128> = positions
+                    .split_words(name, words, separator, usize::MAX)
+                    .into_iter()
+                    .filter_map(|digits| mixed_radix_index(&digits, &radices))
+                    .collect();
+                indices.sort_unstable();
+                indices
+            })
+            .collect()
+    }
+}
+
+/// A view of [`Petnames`] that never uses the same word twice in one name.
+///
+/// Created by [`Petnames::distinct`]. Within a name, words are chosen without
+/// replacement, so there are no names like `very-very-happy-cat`. Words are
+/// compared as strings, so a word in more than one list – e.g. `gold` as both
+/// an adjective and a noun – is used at most once too. Each list is assumed to
+/// have no duplicates of its own.
+///
+/// As with [`Petnames`], a name may contain fewer words than requested if the
+/// lists run out of words.
+///
+#[derive(Clone, Debug)]
+pub struct Distinct<'a, 'w> {
+    petnames: &'a Petnames<'w>,
+    /// Whether each adjective is also an adverb.
+    adjective_is_adverb: Vec<bool>,
+    /// The number of adjectives that are also adverbs.
+    adjectives_in_adverbs: u128,
+    /// Whether each noun is also an adverb, and whether it is also an
+    /// adjective.
+    nouns_in: Vec<(bool, bool)>,
+    /// The number of nouns with each value in `nouns_in`, indexed by whether
+    /// they are also adverbs, then by whether they are also adjectives.
+    nouns_by_overlap: [[u128; 2]; 2],
+}
+
+impl<'a, 'w> Distinct<'a, 'w> {
+    /// Find, once, which words are in more than one list, so that names can
+    /// be counted without searching the lists again.
+    fn new(petnames: &'a Petnames<'w>) -> Self {
+        let adverbs: BTreeSet<&str> = petnames.adverbs.iter().copied().collect();
+        let adjectives: BTreeSet<&str> = petnames.adjectives.iter().copied().collect();
+        let adjective_is_adverb: Vec<bool> =
+            petnames.adjectives.iter().map(|word| adverbs.contains(word)).collect();
+        let adjectives_in_adverbs =
+            adjective_is_adverb.iter().filter(|&&is_adverb| is_adverb).count() as u128;
+        let nouns_in: Vec<(bool, bool)> =
+            petnames.nouns.iter().map(|word| (adverbs.contains(word), adjectives.contains(word))).collect();
+        let mut nouns_by_overlap = [[0; 2]; 2];
+        for &(in_adverbs, in_adjectives) in &nouns_in {
+            nouns_by_overlap[usize::from(in_adverbs)][usize::from(in_adjectives)] += 1;
+        }
+        Self { petnames, adjective_is_adverb, adjectives_in_adverbs, nouns_in, nouns_by_overlap }
+    }
+
+    /// The underlying word lists.
+    pub fn petnames(&self) -> &'a Petnames<'w> {
+        self.petnames
+    }
+
+    /// Calculate the number of names of `words` words with no word repeated.
+    ///
+    /// This can saturate. If the total possible combinations of words exceeds
+    /// `u128::MAX` then this will return `u128::MAX`.
+    pub fn cardinality(&self, words: u8) -> u128 {
+        match words {
+            0 => 0,
+            1 => self.petnames.nouns.len() as u128,
+            _ => {
+                let counts = Counts::new(self, words);
+                // Every noun that is in the same lists ends as many names.
+                [(false, false), (false, true), (true, false), (true, true)]
+                    .into_iter()
+                    .map(|overlap @ (in_adverbs, in_adjectives)| {
+                        match self.nouns_by_overlap[usize::from(in_adverbs)][usize::from(in_adjectives)] {
+                            0 => 0,
+                            nouns => nouns.saturating_mul(counts.with_overlap(overlap)),
+                        }
+                    })
+                    .fold(0, u128::saturating_add)
+            }
+        }
+    }
+
+    /// Create a [`Namer`] that generates petnames with no word repeated.
+    pub fn namer<'b>(&'b self, words: u8, separator: &'b str) -> Namer<'b, Self> {
+        Namer::new(self, words, separator)
+    }
+
+    /// The index of a name of `words` words, given the position of each word
+    /// in its list, or `None` if a word is repeated.
+    ///
+    /// Names are ordered by noun, then by adjective, then by each adverb in
+    /// turn, skipping those that would repeat a word.
+    fn rank(&self, words: u8, digits: &[usize]) -> Option<u128> {
+        let Petnames { adjectives, adverbs, nouns } = self.petnames;
+        let Some((adverb_digits, &[adjective, noun])) = digits.split_last_chunk() else {
+            // A one-word name is just a noun.
+            return digits.first().map(|&noun| noun as u128);
+        };
+        let counts = Counts::new(self, words);
+        let mut index =
+            (0..noun).try_fold(0u128, |index, other| index.checked_add(counts.with_noun(other)))?;
+        if adjectives[adjective] == nouns[noun] {
+            return None;
+        }
+        for other in 0..adjective {
+            index = index.checked_add(counts.with_noun_and_adjective(noun, other))?;
+        }
+        let (adjective, noun) = (adjectives[adjective], nouns[noun]);
+        // Each adverb is a digit whose radix is the number of adverbs left.
+        let mut remaining = remaining_adverbs(adverbs, adjective, noun);
+        let mut adverbs_index = 0u128;
+        for &digit in adverb_digits {
+            let position = remaining.iter().position(|&adverb| adverb == adverbs[digit])?;
+            adverbs_index =
+                adverbs_index.checked_mul(remaining.len() as u128)?.checked_add(position as u128)?;
+            remaining.remove(position);
+        }
+        index.checked_add(adverbs_index)
+    }
+}
+
+impl Generator for Distinct<'_, '_> {
+    /// Every name counted by [`cardinality`][`Distinct::cardinality`] is
+    /// equally likely. If there are none, words are drawn from each list in
+    /// turn, without replacement, until the lists run out.
+    fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
+        if self.cardinality(words) > 0 {
+            crate::generate_uniformly_into(self, buf, rng, words, separator);
+            return;
+        }
+        let mut chosen: Vec<&str> = Vec::with_capacity(words.into());
+        for list in Lists::new(words) {
+            let list = match list {
+                List::Adverb => &self.petnames.adverbs,
+                List::Adjective => &self.petnames.adjectives,
+                List::Noun => &self.petnames.nouns,
+            };
+            if let Some(&word) = list.iter().filter(|word| !chosen.contains(word)).choose(rng) {
+                chosen.push(word);
+            }
+        }
+        for (position, word) in chosen.into_iter().enumerate() {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(word);
+        }
+    }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.petnames.word_ranges(name, words, separator)
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        self.petnames.word_finder(words, separator)
+    }
+}
+
+impl Indexed for Distinct<'_, '_> {
+    fn cardinality(&self, words: u8) -> u128 {
+        Distinct::cardinality(self, words)
+    }
+
+    /// Names are ordered by noun, then by adjective, then by each adverb in
+    /// turn, skipping those that would repeat a word.
+    fn generate_at_into(&self, buf: &mut String, mut index: u128, words: u8, separator: &str) {
+        if index >= self.cardinality(words) {
+            return;
+        }
+        let Petnames { adjectives, adverbs, nouns } = self.petnames;
+        if words == 1 {
+            buf.push_str(nouns[index as usize]);
+            return;
+        }
+        let counts = Counts::new(self, words);
+        let mut skip = |count: u128| match index.checked_sub(count) {
+            Some(rest) => {
+                index = rest;
+                true
+            }
+            None => false,
+        };
+        // These cannot fail: the counts add up to the cardinality.
+        let Some(noun) = (0..nouns.len()).find(|&noun| !skip(counts.with_noun(noun))) else { return };
+        let Some(adjective) =
+            (0..adjectives.len()).find(|&adjective| !skip(counts.with_noun_and_adjective(noun, adjective)))
+        else {
+            return;
+        };
+        let (adjective, noun) = (adjectives[adjective], nouns[noun]);
+        let mut remaining = remaining_adverbs(adverbs, adjective, noun);
+        let radices: Vec<usize> = (0..counts.adverbs_per_name).map(|taken| remaining.len() - taken).collect();
+        for digit in mixed_radix(index, &radices) {
+            buf.push_str(remaining.remove(digit));
+            buf.push_str(separator);
+        }
+        buf.push_str(adjective);
+        buf.push_str(separator);
+        buf.push_str(noun);
+    }
+
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        self.petnames
+            .split_words(name, words, separator, usize::MAX)
+            .into_iter()
+            .find_map(|digits| self.rank(words, &digits))
+    }
+
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        let positions = self.petnames.positions();
+        names
+            .iter()
+            .map(|name| {
+                let mut indices: Vec<u128> = positions
+                    .split_words(name, words, separator, usize::MAX)
+                    .into_iter()
+                    .filter_map(|digits| self.rank(words, &digits))
+                    .collect();
+                indices.sort_unstable();
+                indices
+            })
+            .collect()
+    }
+}
+
+/// Counts of names with no word repeated, for a given number of words.
+struct Counts<'d, 'w> {
+    distinct: &'d Distinct<'d, 'w>,
+    /// The number of adverbs in each name.
+    adverbs_per_name: usize,
+}
+
+impl<'d, 'w> Counts<'d, 'w> {
+    fn new(distinct: &'d Distinct<'d, 'w>, words: u8) -> Self {
+        Self { distinct, adverbs_per_name: usize::from(words).saturating_sub(2) }
+    }
+
+    /// The number of ways to choose the adverbs of a name when `used` of them
+    /// are already used by its adjective and noun.
+    fn adverbs(&self, used: usize) -> u128 {
+        let available = self.distinct.petnames.adverbs.len().saturating_sub(used);
+        (0..self.adverbs_per_name)
+            .map(|taken| available.saturating_sub(taken) as u128)
+            .fold(1, u128::saturating_mul)
+    }
+
+    /// The number of names ending with the noun at `noun`.
+    fn with_noun(&self, noun: usize) -> u128 {
+        self.with_overlap(self.distinct.nouns_in[noun])
+    }
+
+    /// The number of names ending with any one noun that is, or is not, also
+    /// an adverb, and also an adjective.
+    fn with_overlap(&self, (noun_in_adverbs, noun_in_adjectives): (bool, bool)) -> u128 {
+        let Distinct { petnames, adjectives_in_adverbs, .. } = self.distinct;
+        // Adjectives that are, and are not, also adverbs, other than the noun.
+        let adjectives_not_in_adverbs = petnames.adjectives.len() as u128
+            - adjectives_in_adverbs
+            - u128::from(noun_in_adjectives && !noun_in_adverbs);
+        let adjectives_in_adverbs = adjectives_in_adverbs - u128::from(noun_in_adjectives && noun_in_adverbs);
+        let used = usize::from(noun_in_adverbs);
+        adjectives_in_adverbs
+            .saturating_mul(self.adverbs(used + 1))
+            .saturating_add(adjectives_not_in_adverbs.saturating_mul(self.adverbs(used)))
+    }
+
+    /// The number of names ending with the adjective at `adjective` then the
+    /// noun at `noun`.
+    fn with_noun_and_adjective(&self, noun: usize, adjective: usize) -> u128 {
+        let Distinct { petnames, adjective_is_adverb, nouns_in, .. } = self.distinct;
+        if petnames.nouns[noun] == petnames.adjectives[adjective] {
+            0
+        } else {
+            self.adverbs(usize::from(nouns_in[noun].0) + usize::from(adjective_is_adverb[adjective]))
+        }
+    }
+}
+
+/// The adverbs that are neither `adjective` nor `noun`, in order.
+fn remaining_adverbs<'a>(adverbs: &[&'a str], adjective: &str, noun: &str) -> Vec<&'a str> {
+    adverbs.iter().copied().filter(|&adverb| adverb != adjective && adverb != noun).collect()
 }
 
 #[cfg(feature = "default-words")]
@@ -17,10 +17,14 @@
 
 use alloc::borrow::Cow;
 use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Range, RangeBounds};
 
 use rand::{seq::IndexedRandom, RngExt};
 
-use crate::{Generator, List, Lists, Namer};
+use crate::{
+    log2, mixed_radix, mixed_radix_index, splits, style, Generator, Indexed, List, Lists, Lookup, Namer,
+};
 
 /// An attributive adjective, with an optional emphatic (reduplicated) form.
 ///
@@ -80,6 +84,15 @@ impl<'a> Petnames<'a> {
         self.nouns.to_mut().retain(|word| predicate(word));
     }
 
+    /// Keep words whose number of letters – characters, not bytes, since
+    /// Turkish words contain multi-byte code points – is in the given range.
+    ///
+    /// Adjectives are measured by their base form, as with
+    /// [`retain`][`Self::retain`].
+    pub fn retain_letters<R: RangeBounds<usize>>(&mut self, letters: R) {
+        self.retain(|word| letters.contains(&word.chars().count()));
+    }
+
     /// Calculate the cardinality of this generator.
     ///
     /// This can saturate. If the total possible combinations of words exceeds
@@ -98,7 +111,63 @@ impl<'a> Petnames<'a> {
 
     /// Create a [`Namer`] that generates petnames from these word lists.
     pub fn namer<'b>(&'b self, words: u8, separator: &'b str) -> Namer<'b, Self> {
-        Namer { generator: self, words, separator }
+        Namer::new(self, words, separator)
+    }
+
+    /// Find the index of `name` among names of `words` words, if these word
+    /// lists could have produced it.
+    fn locate(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        let digits = self.split(name, words, separator, |_, digit| digit)?;
+        mixed_radix_index(&digits, &self.radices(words))
+    }
+
+    /// Split `name` into `words` words from these lists, if it can be. Each
+    /// word is mapped with `found`, given the word and its position in its
+    /// list.
+    fn split(
+        &self,
+        name: &str,
+        words: u8,
+        separator: &str,
+        found: fn(&str, usize) -> usize,
+    ) -> Option<Vec<usize>> {
+        let adverb = |word: &str| {
+            let position = self.adverbs.iter().position(|candidate| *candidate == word)?;
+            Some(found(word, position))
+        };
+        let adjective = |word: &str| {
+            let position = self
+                .adjectives
+                .iter()
+                .position(|adjective| adjective.word == word || adjective.emphatic == Some(word))?;
+            Some(found(word, position))
+        };
+        let noun = |word: &str| {
+            let position = self.nouns.iter().position(|candidate| *candidate == word)?;
+            Some(found(word, position))
+        };
+        let lookups: Vec<Lookup> = Lists::new(words)
+            .map(|list| match list {
+                List::Adverb => &adverb as Lookup,
+                List::Adjective => &adjective as Lookup,
+                List::Noun => &noun as Lookup,
+            })
+            .collect();
+        if lookups.is_empty() {
+            return None;
+        }
+        splits(name, separator, &lookups, 1).pop()
+    }
+
+    /// The length of the list used for each of `words` words.
+    fn radices(&self, words: u8) -> Vec<usize> {
+        Lists::new(words)
+            .map(|list| match list {
+                List::Adverb => self.adverbs.len(),
+                List::Adjective => self.adjectives.len(),
+                List::Noun => self.nouns.len(),
+            })
+            .collect()
     }
 }
 
@@ -136,6 +205,78 @@ impl Generator for Petnames<'_> {
             };
         }
     }
+
+    /// Turkish has dotted and dotless i: `i` ↔ `İ` and `ı` ↔ `I`.
+    fn uppercase_into(&self, buf: &mut String, word: &str) {
+        for c in word.chars() {
+            match c {
+                'i' => buf.push('İ'),
+                'ı' => buf.push('I'),
+                c => buf.extend(c.to_uppercase()),
+            }
+        }
+    }
+
+    /// Turkish has dotted and dotless i: `i` ↔ `İ` and `ı` ↔ `I`.
+    fn lowercase_into(&self, buf: &mut String, word: &str) {
+        for c in word.chars() {
+            match c {
+                'I' => buf.push('ı'),
+                'İ' => buf.push('i'),
+                c => buf.extend(c.to_lowercase()),
+            }
+        }
+    }
+
+    /// Emphatic adjective forms are found as well as their base forms.
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        match self.split(name, words, separator, |word, _| word.len()) {
+            Some(lengths) => style::joined_ranges(lengths, separator),
+            None => style::split_ranges(name, separator),
+        }
+    }
+}
+
+impl Indexed for Petnames<'_> {
+    fn cardinality(&self, words: u8) -> u128 {
+        Petnames::cardinality(self, words)
+    }
+
+    /// The sum of the entropy of each word, which does not saturate.
+    /// Emphatic adjective forms are not counted, as for
+    /// [`cardinality`][`Self::cardinality`].
+    fn entropy_bits(&self, words: u8) -> f64 {
+        match self.cardinality(words) {
+            0 => 0.0,
+            _ => self.radices(words).into_iter().map(|radix| log2(radix as f64)).sum(),
+        }
+    }
+
+    /// Adjectives are always rendered in their base form here, since the
+    /// emphatic forms are not counted as distinct combinations.
+    fn generate_at_into(&self, buf: &mut String, index: u128, words: u8, separator: &str) {
+        if index >= self.cardinality(words) {
+            return;
+        }
+        for (position, (list, digit)) in
+            Lists::new(words).zip(mixed_radix(index, &self.radices(words))).enumerate()
+        {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(match list {
+                List::Adverb => self.adverbs[digit],
+                List::Adjective => self.adjectives[digit].word,
+                List::Noun => self.nouns[digit],
+            });
+        }
+    }
+
+    /// Emphatic adjective forms are recognised, and share the index of their
+    /// base form.
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        self.locate(name, words, separator)
+    }
 }
 
 #[cfg(test)]
@@ -225,6 +366,32 @@ mod tests {
         assert!(!turkish.adjectives.iter().any(|adjective| adjective.word.contains('=')));
     }
 
+    #[test]
+    fn unique_iter_yields_every_name_once() {
+        use alloc::collections::BTreeSet;
+        use rand::SeedableRng;
+        let turkish = sample();
+        let namer = turkish.namer(3, "-");
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+        let names: alloc::vec::Vec<alloc::string::String> = namer.unique_iter(&mut rng).collect();
+        let distinct: BTreeSet<&str> = names.iter().map(|name| name.as_str()).collect();
+        assert_eq!(names.len(), 8);
+        assert_eq!(distinct.len(), 8);
+        // Emphatic forms are not part of the enumeration.
+        assert!(!names.iter().any(|name| name.contains("kıpkırmızı")));
+    }
+
+    #[test]
+    fn index_of_recognises_emphatic_forms() {
+        use crate::Indexed;
+        let turkish = sample();
+        let index = turkish.index_of("kırmızı-kedi", 2, "-").unwrap();
+        assert_eq!(turkish.index_of("kıpkırmızı-kedi", 2, "-"), Some(index));
+        assert_eq!(turkish.name_at(index, 2, "-").as_deref(), Some("kırmızı-kedi"));
+        assert_eq!(turkish.index_of("kırmızı-at", 2, "-"), None);
+        assert_eq!(turkish.index_of("kırmızı-kedi", 3, "-"), None);
+    }
+
     #[test]
     fn retain_filters_all_lists() {
         let mut turkish = sample();
@@ -235,4 +402,21 @@ mod tests {
         assert!(turkish.nouns.iter().all(|word| word.chars().count() <= 5));
         assert_eq!(turkish.adverbs.len(), 1); // only "çok" remains.
     }
+
+    #[test]
+    fn style_uses_turkish_case_rules() {
+        use crate::{Generator, Style};
+        let turkish = Petnames {
+            adjectives: Cow::Owned(vec![Adjective::plain("ılık")]),
+            adverbs: Cow::Owned(vec![]),
+            nouns: Cow::Owned(vec!["inci"]),
+        };
+        let namer = turkish.namer(2, "_").with_style(Style::ScreamingSnake);
+        assert_eq!(namer.name_for_key(b"").as_deref(), Some("ILIK_İNCİ"));
+        let namer = turkish.namer(2, "").with_style(Style::Pascal);
+        assert_eq!(namer.name_for_key(b"").as_deref(), Some("Ilıkİnci"));
+        let mut buf = alloc::string::String::new();
+        turkish.lowercase_into(&mut buf, "ILIK İNCİ");
+        assert_eq!(buf, "ılık inci");
+    }
 }
@@ -0,0 +1,366 @@
+//! Names no longer than a given number of characters, as made by
+//! [`Namer::with_max_len`].
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use rand::RngExt;
+
+use crate::{Alliterations, Generator, Indexed, List, Lists, Namer, Petnames, Words};
+
+/// An [`Indexed`] generator that can count, and generate, its names by their
+/// length, for [`Namer::with_max_len`].
+///
+/// Lengths are counted in characters – Unicode scalar values – and do not
+/// include separators.
+pub trait Lengths: Indexed {
+    /// Count the names of `words` words by the number of characters between
+    /// them, from 0 to `max_letters` inclusive, so that they can then be
+    /// generated by length without counting them again.
+    fn by_letters(&self, words: u8, max_letters: usize) -> Box<dyn ByLetters + '_>;
+
+    /// The number of distinct names of `words` words with each number of
+    /// characters between them, from 0 to `max_letters` inclusive.
+    ///
+    /// This can saturate, as [`Indexed::cardinality`] can.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Lengths;
+    /// let petnames = petname::Petnames::new("big fat", "", "ant bee gnu");
+    /// assert_eq!(petnames.cardinality_by_letters(2, 7), [0, 0, 0, 0, 0, 0, 6, 0]);
+    /// assert_eq!(petnames.cardinality_by_letters(1, 2), [0, 0, 0]);
+    /// ```
+    ///
+    fn cardinality_by_letters(&self, words: u8, max_letters: usize) -> Vec<u128> {
+        self.by_letters(words, max_letters).cardinality().to_vec()
+    }
+}
+
+/// Names counted by their length, as by [`Lengths::by_letters`].
+pub trait ByLetters {
+    /// The number of distinct names with each number of characters, from 0
+    /// to the maximum inclusive.
+    fn cardinality(&self) -> &[u128];
+
+    /// Generate the petname at `index` among the names with `letters`
+    /// characters, into a given [`String`] buffer.
+    ///
+    /// Like [`Indexed::generate_at_into`], this method does not clear the
+    /// buffer, and nothing is pushed if `index` or `letters` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Lengths;
+    /// let petnames = petname::Petnames::new("big fat", "", "ant hippo");
+    /// let by_letters = petnames.by_letters(2, 8);
+    /// let mut buf = String::new();
+    /// by_letters.generate_into(&mut buf, 1, 8, "-");
+    /// assert_eq!(buf, "fat-hippo");
+    /// ```
+    ///
+    fn generate_into(&self, buf: &mut String, index: u128, letters: usize, separator: &str);
+}
+
+impl Lengths for Petnames<'_> {
+    fn by_letters(&self, words: u8, max_letters: usize) -> Box<dyn ByLetters + '_> {
+        Box::new(ByLength::new(self, words, max_letters))
+    }
+}
+
+impl<K: Ord> Lengths for Alliterations<'_, K> {
+    /// Groups are laid end to end, in order of their first letter, as with
+    /// [`Indexed::generate_at_into`].
+    fn by_letters(&self, words: u8, max_letters: usize) -> Box<dyn ByLetters + '_> {
+        Box::new(EndToEnd::new(
+            self.groups.values().map(|group| group.by_letters(words, max_letters)).collect(),
+            max_letters,
+        ))
+    }
+}
+
+/// Names counted by length in each of several groups, laid end to end.
+struct EndToEnd<'a> {
+    groups: Vec<Box<dyn ByLetters + 'a>>,
+    cardinality: Vec<u128>,
+}
+
+impl<'a> EndToEnd<'a> {
+    fn new(groups: Vec<Box<dyn ByLetters + 'a>>, max_letters: usize) -> Self {
+        let cardinality = groups.iter().fold(vec![0u128; max_letters + 1], |mut counts, group| {
+            for (count, &names) in counts.iter_mut().zip(group.cardinality()) {
+                *count = count.saturating_add(names);
+            }
+            counts
+        });
+        Self { groups, cardinality }
+    }
+}
+
+impl ByLetters for EndToEnd<'_> {
+    fn cardinality(&self) -> &[u128] {
+        &self.cardinality
+    }
+
+    fn generate_into(&self, buf: &mut String, mut index: u128, letters: usize, separator: &str) {
+        for group in &self.groups {
+            let cardinality = group.cardinality().get(letters).copied().unwrap_or(0);
+            if index < cardinality {
+                group.generate_into(buf, index, letters, separator);
+                return;
+            }
+            index -= cardinality;
+        }
+    }
+}
+
+/// The words of each list of a name, grouped by length, and the number of
+/// ways to finish a name from each list onwards with each number of letters.
+struct ByLength<'a> {
+    /// For each list in turn, the words of each length, in their order.
+    lists: Vec<Vec<Vec<&'a str>>>,
+    /// For each list in turn, and one past the last, the number of ways to
+    /// choose words from that list onwards with each number of letters.
+    tails: Vec<Vec<u128>>,
+}
+
+impl<'a> ByLength<'a> {
+    fn new(petnames: &Petnames<'a>, words: u8, max_letters: usize) -> Self {
+        let group = |words: &Words<'a>| {
+            let mut lengths = vec![Vec::new(); max_letters + 1];
+            for &word in words.iter() {
+                if let Some(chosen) = lengths.get_mut(word.chars().count()) {
+                    chosen.push(word);
+                }
+            }
+            lengths
+        };
+        let lists: Vec<Vec<Vec<&str>>> = Lists::new(words)
+            .map(|list| match list {
+                List::Adverb => group(&petnames.adverbs),
+                List::Adjective => group(&petnames.adjectives),
+                List::Noun => group(&petnames.nouns),
+            })
+            .collect();
+
+        // There are no names without words, as with `Petnames::cardinality`.
+        let mut last = vec![0; max_letters + 1];
+        last[0] = u128::from(!lists.is_empty());
+        let mut tails = vec![last];
+        for lengths in lists.iter().rev() {
+            let next = &tails[0];
+            let tail = (0..=max_letters)
+                .map(|letters| {
+                    (0..=letters).fold(0u128, |ways, length| {
+                        (lengths[length].len() as u128)
+                            .saturating_mul(next[letters - length])
+                            .saturating_add(ways)
+                    })
+                })
+                .collect();
+            tails.insert(0, tail);
+        }
+        Self { lists, tails }
+    }
+}
+
+impl ByLetters for ByLength<'_> {
+    fn cardinality(&self) -> &[u128] {
+        &self.tails[0]
+    }
+
+    fn generate_into(&self, buf: &mut String, mut index: u128, letters: usize, separator: &str) {
+        if index >= self.tails[0].get(letters).copied().unwrap_or(0) {
+            return;
+        }
+        let mut remaining = letters;
+        for (position, lengths) in self.lists.iter().enumerate() {
+            // The names with this word as short as possible come first.
+            for (length, chosen) in lengths.iter().enumerate().take(remaining + 1) {
+                let ways = self.tails[position + 1][remaining - length];
+                let names = (chosen.len() as u128).saturating_mul(ways);
+                if index < names {
+                    if position > 0 {
+                        buf.push_str(separator);
+                    }
+                    buf.push_str(chosen[(index / ways) as usize]);
+                    index %= ways;
+                    remaining -= length;
+                    break;
+                }
+                index -= names;
+            }
+        }
+    }
+}
+
+impl<'a, G: Lengths + 'a> Namer<'a, G> {
+    /// Generate only names of at most `max_len` characters, counting the
+    /// separators and the suffix, if any.
+    ///
+    /// Names are chosen uniformly from among those that fit, without
+    /// rejecting names that do not, so this is as fast however few names fit,
+    /// and [`cardinality`][`Self::cardinality`] counts exactly those names.
+    /// Like the style, this applies to everything except
+    /// [`encode`][`Self::encode`] and [`decode`][`Self::decode`]. A style that
+    /// changes the length of a word, e.g. by upper-casing `ß` as `SS`, is not
+    /// accounted for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-rng")] {
+    /// let petnames = petname::Petnames::new("big tiny", "", "ant hippopotamus");
+    /// let namer = petnames.namer(2, "-").with_max_len(8);
+    /// assert_eq!(namer.cardinality(), 2);
+    /// let name = namer.iter(&mut rand::rng()).next().unwrap();
+    /// assert!(name == "big-ant" || name == "tiny-ant");
+    /// # }
+    /// ```
+    ///
+    pub fn with_max_len(self, max_len: usize) -> Self {
+        // Names are counted once, here, for the longest words that could
+        // fit; a suffix, if any, is counted as each name is generated.
+        let letters = max_len.saturating_sub(self.separators());
+        Self { max_len: Some((max_len, self.generator.by_letters(self.words, letters))), ..self }
+    }
+}
+
+impl<G: Generator + ?Sized> Namer<'_, G> {
+    /// The number of characters in the separators between words.
+    pub(crate) fn separators(&self) -> usize {
+        self.separator.chars().count().saturating_mul(usize::from(self.words).saturating_sub(1))
+    }
+
+    /// The number of names with each number of letters that fit within the
+    /// maximum length, once separators and the suffix are counted, and the
+    /// names counted by length to generate them from; `None` if there is no
+    /// maximum length.
+    pub(crate) fn by_letters(&self) -> Option<(&[u128], &dyn ByLetters)> {
+        let (max_len, ref by_letters) = *self.max_len.as_ref()?;
+        let suffix = self
+            .suffix
+            .filter(|suffix| suffix.length() > 0)
+            .map_or(0, |suffix| self.separator.chars().count() + usize::from(suffix.length()));
+        let counts = match max_len.checked_sub(self.separators() + suffix) {
+            Some(letters) => by_letters.cardinality().get(..=letters).unwrap_or_default(),
+            None => &[],
+        };
+        Some((counts, by_letters.as_ref()))
+    }
+
+    /// The number of names, not counting the suffix, that fit within the
+    /// maximum length; `None` if there is no maximum length.
+    pub(crate) fn max_len_cardinality(&self) -> Option<u128> {
+        self.by_letters()
+            .map(|(counts, _)| counts.iter().fold(0u128, |total, &names| total.saturating_add(names)))
+    }
+
+    /// Push the words of a name onto `buf`, joined by `separator`, fitting
+    /// within the maximum length, if any.
+    pub(crate) fn generate_words_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, separator: &str) {
+        match self.by_letters() {
+            None => self.generator.generate_into(buf, rng, self.words, separator),
+            Some((counts, by_letters)) => {
+                match counts.iter().fold(0u128, |total, &names| total.saturating_add(names)) {
+                    0 => {}
+                    total => push_at(buf, rng.random_range(0..total), separator, counts, by_letters),
+                }
+            }
+        }
+    }
+}
+
+impl<G: Indexed + ?Sized> Namer<'_, G> {
+    /// Push the words of the name at `index` onto `buf`, joined by
+    /// `separator`. With a maximum length, names are ordered by their number
+    /// of letters, shortest first.
+    pub(crate) fn generate_words_at_into(&self, buf: &mut String, index: u128, separator: &str) {
+        match self.by_letters() {
+            None => self.generator.generate_at_into(buf, index, self.words, separator),
+            Some((counts, by_letters)) => push_at(buf, index, separator, counts, by_letters),
+        }
+    }
+}
+
+/// Push the name at `index` among those counted by `counts` onto `buf`.
+fn push_at(buf: &mut String, mut index: u128, separator: &str, counts: &[u128], by_letters: &dyn ByLetters) {
+    for (letters, &names) in counts.iter().enumerate() {
+        if index < names {
+            by_letters.generate_into(buf, index, letters, separator);
+            return;
+        }
+        index -= names;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use rand::SeedableRng;
+
+    use super::Lengths;
+    use crate::{Alliterations, Alphabet, Petnames, Suffix};
+
+    #[test]
+    fn generate_by_letters_enumerates_each_length() {
+        let petnames = Petnames::new("big tiny", "so very", "ant bee hippo");
+        let by_letters = petnames.by_letters(3, 14);
+        for letters in 0..=14 {
+            let cardinality = by_letters.cardinality()[letters];
+            assert_eq!(petnames.cardinality_by_letters(3, letters)[letters], cardinality);
+            let mut names: Vec<String> = (0..cardinality)
+                .map(|index| {
+                    let mut buf = String::new();
+                    by_letters.generate_into(&mut buf, index, letters, "-");
+                    buf
+                })
+                .collect();
+            assert!(names.iter().all(|name| name.chars().filter(|&c| c != '-').count() == letters));
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(names.len() as u128, cardinality);
+            let mut buf = String::new();
+            by_letters.generate_into(&mut buf, cardinality, letters, "-");
+            assert_eq!(buf, "");
+        }
+        let total: u128 = petnames.cardinality_by_letters(3, 14).iter().sum();
+        assert_eq!(total, petnames.cardinality(3));
+    }
+
+    #[test]
+    fn cardinality_by_letters_sums_alliterations() {
+        let alliterations: Alliterations = Petnames::new("able bold", "", "ant bee bison").into();
+        assert_eq!(alliterations.cardinality_by_letters(2, 9), [0, 0, 0, 0, 0, 0, 0, 2, 0, 1]);
+        let mut buf = String::new();
+        alliterations.by_letters(2, 9).generate_into(&mut buf, 1, 7, "-");
+        assert_eq!(buf, "bold-bee");
+    }
+
+    #[test]
+    fn namer_counts_separators_and_suffix() {
+        let petnames = Petnames::new("big tiny", "", "ant hippopotamus");
+        let namer = petnames.namer(2, "--").with_max_len(9);
+        assert_eq!(namer.cardinality(), 2);
+        assert_eq!(namer.with_suffix(Suffix::new(Alphabet::Digits, 2)).cardinality(), 0);
+        let namer = petnames.namer(2, "-").with_suffix(Suffix::new(Alphabet::Digits, 2)).with_max_len(10);
+        assert_eq!(namer.cardinality(), 100);
+        assert_eq!(petnames.namer(0, "-").with_max_len(10).cardinality(), 0);
+    }
+
+    #[test]
+    fn namer_generates_only_names_that_fit() {
+        let petnames = Petnames::new("big tiny enormous", "", "ant bee hippopotamus");
+        let namer = petnames.namer(2, "-").with_max_len(8);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let mut names: Vec<String> = namer.iter(&mut rng).take(100).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names, ["big-ant", "big-bee", "tiny-ant", "tiny-bee"]);
+        let mut unique: Vec<String> = namer.unique_iter(&mut rng).collect();
+        unique.sort_unstable();
+        assert_eq!(unique, names);
+    }
+}
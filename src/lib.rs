@@ -76,6 +76,37 @@
 //! # } }
 //! ```
 //!
+//! ## Other grammars
+//!
+//! [`Petnames`] always makes names of adverbs, an adjective, and a noun. A
+//! [`Grammar`] makes names from any number of named word lists, in any order,
+//! such as `{color}-{animal}`. The [`english!`] macro embeds one when given
+//! `slots`:
+//!
+//! ```rust
+//! # #[cfg(feature = "macros")] {
+//! let grammar = petname::english!("words/small", slots = "adjectives nouns");
+//! assert_eq!(grammar.cardinality(2), petname::Petnames::small().cardinality(2));
+//! # }
+//! ```
+//!
+//! ## Weighted words
+//!
+//! Every word in a [`Petnames`] list is equally likely. A [`Weighted`] chooses
+//! each word in proportion to its weight instead, so that common words can come
+//! up more often than obscure ones. The [`english!`] macro embeds one when
+//! given the `weighted` flag, reading weights written like `fox:2.5`:
+//!
+//! ```rust
+//! # #[cfg(feature = "macros")] {
+//! let weighted = petname::english!("words/small", weighted);
+//! // Without any weights, every word is equally likely.
+//! let petnames = petname::Petnames::small();
+//! use petname::Indexed;
+//! assert!((weighted.entropy_bits(2) - petnames.entropy_bits(2)).abs() < 1e-9);
+//! # }
+//! ```
+//!
 //! ## Alliterating
 //!
 //! There is another way to generate alliterative petnames, useful in particular
@@ -92,6 +123,34 @@
 //! # }
 //! ```
 //!
+//! ## Unique names
+//!
+//! [`Namer::iter`] may repeat itself. [`Namer::unique_iter`] never does: it
+//! visits every possible name exactly once, in a random order, and then stops.
+//! It works with any generator that implements [`Indexed`], and uses the same
+//! small amount of memory however many names there are:
+//!
+//! ```rust
+//! # #[cfg(all(feature = "default-rng", feature = "default-words"))] {
+//! let petnames = petname::Petnames::small();
+//! let namer = petnames.namer(2, "-");
+//! let names: Vec<String> = namer.unique_iter(&mut rand::rng()).take(1000).collect();
+//! # }
+//! ```
+//!
+//! The same numbering is available directly, with [`Indexed::name_at`] and
+//! [`Indexed::index_of`]. Store compact integers, and render names only where
+//! people need to see them:
+//!
+//! ```rust
+//! use petname::Indexed;
+//! # #[cfg(feature = "default-words")] {
+//! let petnames = petname::Petnames::default();
+//! let name = petnames.name_at(123456, 3, "-").expect("index out of range");
+//! assert_eq!(petnames.index_of(&name, 3, "-"), Some(123456));
+//! # }
+//! ```
+//!
 //! # The [`Generator`] trait
 //!
 //! Both [`Petnames`] and [`Alliterations`] implement [`Generator`]. It's
@@ -117,9 +176,10 @@ extern crate alloc;
 #[cfg(feature = "macros")]
 extern crate self as petname;
 
-use alloc::{borrow::Cow, collections::BTreeMap, string::String, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::ops::Range;
 
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, RngExt};
 
 /// Convenience function to generate a new (English) petname from default word
 /// lists.
@@ -145,6 +205,39 @@ pub use petname_macros::turkish;
 // Language-specific petname generators.
 pub mod lang;
 
+mod batch;
+mod blocklist;
+mod excluding;
+mod folding;
+mod grammar;
+mod hash;
+mod lengths;
+mod mnemonic;
+mod permutation;
+mod phonetic;
+mod profile;
+mod rhymes;
+mod style;
+mod suffix;
+mod template;
+mod weighted;
+
+use crate::excluding::Excluded;
+
+pub use crate::batch::{BatchError, DistinctBatch};
+pub use crate::blocklist::{Blocking, Blocklist, BlocklistError};
+pub use crate::folding::Folding;
+pub use crate::grammar::{Grammar, GrammarError};
+pub use crate::lengths::{ByLetters, Lengths};
+pub use crate::mnemonic::MnemonicError;
+pub use crate::phonetic::{initial_sound, phonetic_key};
+pub use crate::profile::{Profile, ProfileError, Profiled};
+pub use crate::rhymes::{rhyme_key, Rhymes};
+pub use crate::style::Style;
+pub use crate::suffix::{Alphabet, Suffix};
+pub use crate::template::{Template, TemplateError};
+pub use crate::weighted::{Weighted, WeightsError};
+
 /// Re-export [`lang::english::Petnames`] as the default.
 pub use crate::lang::english::Petnames;
 
@@ -165,6 +258,257 @@ pub trait Generator {
     /// if one or more of the word lists are empty.
     ///
     fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str);
+
+    /// Push `word` in upper case onto `buf`, following the rules of this
+    /// generator's language.
+    ///
+    /// The default uses Unicode's default case mapping, as
+    /// [`str::to_uppercase`] does. Used by [`Style`].
+    ///
+    fn uppercase_into(&self, buf: &mut String, word: &str) {
+        buf.push_str(&word.to_uppercase());
+    }
+
+    /// Push `word` in lower case onto `buf`, following the rules of this
+    /// generator's language.
+    ///
+    /// The default uses Unicode's default case mapping, as
+    /// [`str::to_lowercase`] does. Used by [`Style`].
+    ///
+    fn lowercase_into(&self, buf: &mut String, word: &str) {
+        buf.push_str(&word.to_lowercase());
+    }
+
+    /// Find the words of `name`, a name of `words` words that this generator
+    /// produced with `separator`, as ranges of bytes in `name`.
+    ///
+    /// The default splits `name` on `separator`, or, if it is empty, treats
+    /// `name` as one word. Used by [`Style`], which changes the case of each
+    /// word and leaves everything between them as it is.
+    ///
+    fn word_ranges(&self, name: &str, _words: u8, separator: &str) -> Vec<Range<usize>> {
+        style::split_ranges(name, separator)
+    }
+
+    /// Prepare to find the words of many names of `words` words that this
+    /// generator produced with `separator`, as
+    /// [`word_ranges`][`Self::word_ranges`] finds them for one.
+    ///
+    /// The default calls `word_ranges` for each name. Generators that look
+    /// words up in their lists override it to prepare their lists for lookup
+    /// once, rather than searching them for every name. Used by [`Style`].
+    ///
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        Box::new(move |name| self.word_ranges(name, words, separator))
+    }
+}
+
+/// Finds the words of a name as ranges of bytes in that name, as prepared by
+/// [`Generator::word_finder`].
+pub type WordFinder<'a> = Box<dyn Fn(&str) -> Vec<Range<usize>> + 'a>;
+
+/// A [`Generator`] whose names can be enumerated.
+///
+/// For a given number of words, each name that the generator can produce has
+/// an _index_ in `0..cardinality(words)`. This is what lets
+/// [`Namer::unique_iter`] visit every name exactly once without remembering
+/// the names it has already produced.
+///
+/// This trait is [object-safe] too.
+///
+/// [object-safe]:
+///     https://doc.rust-lang.org/reference/items/traits.html#object-safety
+///
+pub trait Indexed: Generator {
+    /// Calculate the number of distinct names of `words` words.
+    ///
+    /// This can saturate. If the total possible combinations of words exceeds
+    /// `u128::MAX` then this will return `u128::MAX`.
+    fn cardinality(&self, words: u8) -> u128;
+
+    /// Generate the petname at `index` into a given [`String`] buffer.
+    ///
+    /// Like [`Generator::generate_into`], this method does not clear the
+    /// buffer. Nothing is pushed if `index` is not less than
+    /// [`cardinality`][`Self::cardinality`].
+    ///
+    fn generate_at_into(&self, buf: &mut String, index: u128, words: u8, separator: &str);
+
+    /// Find the index of `name` among names of `words` words, if this
+    /// generator could have produced it.
+    ///
+    /// This is the inverse of [`name_at`][`Self::name_at`]. Names of each
+    /// number of words are indexed from zero, so an index is only meaningful
+    /// alongside its number of words. That number cannot always be inferred
+    /// from the name: when a word contains the separator, as `x-ray` does,
+    /// `x-ray-fish` could be a name of two words or of three, at different
+    /// indices. So `name` must have exactly `words` words. If it can be split
+    /// into that many words in more than one way, the split with the shortest
+    /// leading words is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// let petnames = petname::Petnames::new("fair", "very", "cat dog");
+    /// assert_eq!(petnames.index_of("fair-dog", 2, "-"), Some(1));
+    /// assert_eq!(petnames.index_of("fair-dog", 3, "-"), None);
+    /// assert_eq!(petnames.index_of("fair-fox", 2, "-"), None);
+    /// ```
+    ///
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128>;
+
+    /// Find every index of each of `names` among names of `words` words, in
+    /// ascending order for each name.
+    ///
+    /// A name is at more than one index when it can be split into words in
+    /// more than one way: `x-ray-fish` is both `x-ray` + `fish` and `x` +
+    /// `ray-fish`. [`index_of`][`Self::index_of`] finds only one of these. The
+    /// default finds only that one too, so generators whose words can contain
+    /// the separator override it. Names are looked up together so that
+    /// generators can prepare their word lists for lookup once, not once for
+    /// each name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// let petnames = petname::Petnames::new("x-ray x", "", "fish ray-fish");
+    /// assert_eq!(petnames.indices_of(&["x-ray-fish", "x-fish", "x-ray"], 2, "-"), [vec![0, 3], vec![2], vec![]]);
+    /// assert_eq!(petnames.index_of("x-ray-fish", 2, "-"), Some(3));
+    /// ```
+    ///
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        names.iter().map(|name| self.index_of(name, words, separator).into_iter().collect()).collect()
+    }
+
+    /// Generate the petname at `index`.
+    ///
+    /// Returns `None` if `index` is not less than
+    /// [`cardinality`][`Self::cardinality`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// let petnames = petname::Petnames::new("fair", "very", "cat dog");
+    /// assert_eq!(petnames.name_at(1, 2, "-"), Some("fair-dog".into()));
+    /// assert_eq!(petnames.name_at(2, 2, "-"), None);
+    /// ```
+    ///
+    fn name_at(&self, index: u128, words: u8, separator: &str) -> Option<String> {
+        let mut buf = String::new();
+        self.generate_at_into(&mut buf, index, words, separator);
+        (!buf.is_empty()).then_some(buf)
+    }
+
+    /// Generate a petname deterministically from `key`.
+    ///
+    /// The same key always produces the same name from the same word lists,
+    /// in this and future versions of this crate. This makes it possible to
+    /// give, say, every commit hash or UUID a memorable alias without keeping
+    /// a lookup table.
+    ///
+    /// The mapping is specified as follows: `key` is hashed with the 128-bit
+    /// [FNV-1a] hash, and the hash, reduced modulo
+    /// [`cardinality`][`Self::cardinality`], is passed to
+    /// [`name_at`][`Self::name_at`]. Different keys can produce the same
+    /// name; how often depends on the cardinality.
+    ///
+    /// Returns `None` if the cardinality is zero.
+    ///
+    /// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// # #[cfg(feature = "default-words")] {
+    /// let petnames = petname::Petnames::default();
+    /// let name = petnames.name_for_key(b"4c6fd1a", 3, "-");
+    /// assert_eq!(name, petnames.name_for_key(b"4c6fd1a", 3, "-"));
+    /// # }
+    /// ```
+    ///
+    fn name_for_key(&self, key: &[u8], words: u8, separator: &str) -> Option<String> {
+        match self.cardinality(words) {
+            0 => None,
+            cardinality => self.name_at(hash::fnv1a_128(key) % cardinality, words, separator),
+        }
+    }
+
+    /// The entropy, in bits, of a name of `words` words chosen uniformly.
+    ///
+    /// By default this is `log2(cardinality)`, so it saturates at 128 bits
+    /// when the cardinality does. [`Petnames`], [`Alliterations`], and the
+    /// Turkish `Petnames` override it so that it does not saturate. Returns 0
+    /// when there are no names to choose from.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// let petnames = petname::Petnames::new("fair", "very", "cat dog");
+    /// assert_eq!(petnames.entropy_bits(1), 1.0);
+    /// ```
+    ///
+    fn entropy_bits(&self, words: u8) -> f64 {
+        match self.cardinality(words) {
+            0 => 0.0,
+            cardinality => log2(cardinality as f64),
+        }
+    }
+
+    /// An upper bound on the probability that `names` names of `words` words,
+    /// chosen uniformly and independently, are not all different.
+    ///
+    /// This is the [birthday bound], `n(n-1) / 2N`, capped at 1.
+    ///
+    /// [birthday bound]: https://en.wikipedia.org/wiki/Birthday_problem
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// let petnames = petname::Petnames::new("fair", "very", "cat dog");
+    /// assert_eq!(petnames.collision_probability(1, 1), 0.0);
+    /// assert_eq!(petnames.collision_probability(1, 2), 0.5);
+    /// assert_eq!(petnames.collision_probability(1, 3), 1.0);
+    /// ```
+    ///
+    fn collision_probability(&self, words: u8, names: u128) -> f64 {
+        let (names, cardinality) = (names as f64, self.cardinality(words) as f64);
+        if names < 2.0 {
+            0.0
+        } else {
+            (names * (names - 1.0) / (2.0 * cardinality)).min(1.0)
+        }
+    }
+
+    /// Create a [`Namer`] with the fewest words that give at least `min_bits`
+    /// bits of entropy per name.
+    ///
+    /// Returns `None` if no number of words is enough.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::Indexed;
+    /// # #[cfg(feature = "default-words")] {
+    /// let petnames = petname::Petnames::default();
+    /// let namer = petnames.namer_for_entropy(24.0, "-").unwrap();
+    /// assert_eq!(namer.words(), 3);
+    /// # }
+    /// ```
+    ///
+    fn namer_for_entropy<'a>(&'a self, min_bits: f64, separator: &'a str) -> Option<Namer<'a, Self>>
+    where
+        Self: Sized,
+    {
+        (1..=u8::MAX)
+            .find(|&words| self.cardinality(words) > 0 && self.entropy_bits(words) >= min_bits)
+            .map(|words| Namer::new(self, words, separator))
+    }
 }
 
 /// A configured petname generator.
@@ -194,9 +538,80 @@ pub struct Namer<'a, G: ?Sized> {
     generator: &'a G,
     words: u8,
     separator: &'a str,
+    style: Option<(Style, WordFinder<'a>)>,
+    suffix: Option<Suffix>,
+    max_len: Option<(usize, Box<dyn ByLetters + 'a>)>,
+    excluded: Option<Excluded<G>>,
 }
 
 impl<'a, G: Generator + ?Sized> Namer<'a, G> {
+    /// Create a [`Namer`] for any [`Generator`].
+    ///
+    /// The generators in this crate have their own `namer` methods, which are
+    /// more convenient; this is for use with other generators, or when the
+    /// generator is only known by its traits.
+    ///
+    pub fn new(generator: &'a G, words: u8, separator: &'a str) -> Self {
+        Self { generator, words, separator, style: None, suffix: None, max_len: None, excluded: None }
+    }
+
+    /// Change the case of each word in generated names.
+    ///
+    /// Words are still separated by this namer's separator; see
+    /// [`Style::separator`] for the separator conventionally used with each
+    /// style. The style applies to [`generate_into`][`Self::generate_into`],
+    /// [`iter`][`Self::iter`], [`unique_iter`][`Self::unique_iter`], and
+    /// [`name_for_key`][`Self::name_for_key`], but not to
+    /// [`encode`][`Self::encode`] and [`decode`][`Self::decode`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-rng")] {
+    /// use petname::Style;
+    /// let petnames = petname::Petnames::new("fair", "", "cat");
+    /// let namer = petnames.namer(2, Style::Pascal.separator()).with_style(Style::Pascal);
+    /// assert_eq!(namer.iter(&mut rand::rng()).next().as_deref(), Some("FairCat"));
+    /// # }
+    /// ```
+    ///
+    pub fn with_style(self, style: Style) -> Self {
+        // Words are found in each name with a finder prepared once, here.
+        let word_finder = self.generator.word_finder(self.words, self.separator);
+        Self { style: Some((style, word_finder)), ..self }
+    }
+
+    /// Append a random suffix, joined by this namer's separator, to each
+    /// generated name.
+    ///
+    /// The suffix is appended after any [`Style`] is applied, so it is not
+    /// restyled. Like the style, it applies to everything except
+    /// [`encode`][`Self::encode`] and [`decode`][`Self::decode`], and it is
+    /// counted by [`cardinality`][`Self::cardinality`] and
+    /// [`entropy_bits`][`Self::entropy_bits`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-rng")] {
+    /// use petname::{Alphabet, Suffix};
+    /// let petnames = petname::Petnames::new("fair", "", "cat");
+    /// let namer = petnames.namer(2, "-").with_suffix(Suffix::new(Alphabet::Consonants, 3));
+    /// assert_eq!(namer.cardinality(), 8000);
+    /// let name = namer.iter(&mut rand::rng()).next().unwrap();
+    /// assert_eq!(name.len(), "fair-cat-xyz".len());
+    /// # }
+    /// ```
+    ///
+    pub fn with_suffix(self, suffix: Suffix) -> Self {
+        Self { suffix: Some(suffix), ..self }
+    }
+
+    /// The number of words in each name.
+    pub fn words(&self) -> u8 {
+        self.words
+    }
+
     /// Generate a petname into a given [`String`] buffer.
     ///
     /// This can be more efficient than [`iter`][`Self::iter`] when generating
@@ -241,7 +656,40 @@ impl<'a, G: Generator + ?Sized> Namer<'a, G> {
     /// ```
     ///
     pub fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng) {
-        self.generator.generate_into(buf, rng, self.words, self.separator);
+        match self.excluded {
+            None => self.generate_any_into(buf, rng),
+            Some(ref excluded) => self.generate_remaining_into(buf, rng, excluded),
+        }
+    }
+
+    /// Push a name onto `buf`, whether or not it is excluded.
+    fn generate_any_into(&self, buf: &mut String, rng: &mut dyn rand::Rng) {
+        let start = buf.len();
+        self.generate_styled(buf, |buf| self.generate_words_into(buf, rng, self.separator));
+        if let Some(suffix) = self.suffix_for(buf, start) {
+            suffix.generate_into(buf, rng);
+        }
+    }
+
+    /// The suffix to push, if any, having pushed the separator before it.
+    /// There is no suffix if no name was pushed since `start`.
+    fn suffix_for(&self, buf: &mut String, start: usize) -> Option<Suffix> {
+        let suffix = self.suffix.filter(|suffix| suffix.length() > 0 && buf.len() > start)?;
+        buf.push_str(self.separator);
+        Some(suffix)
+    }
+
+    /// Push a name onto `buf` with `generate`, then apply this namer's style,
+    /// if any, to each of its words.
+    fn generate_styled(&self, buf: &mut String, generate: impl FnOnce(&mut String)) {
+        match self.style {
+            None => generate(buf),
+            Some((style, ref word_finder)) => {
+                let mut name = String::new();
+                generate(&mut name);
+                style.restyle_into(self.generator, buf, &name, &word_finder(&name));
+            }
+        }
     }
 
     /// Iterator yielding petnames.
@@ -268,36 +716,180 @@ impl<'a, G: Generator + ?Sized> Namer<'a, G> {
     }
 }
 
+impl<'a, G: Indexed + ?Sized> Namer<'a, G> {
+    /// Iterator yielding every petname exactly once, in a random order.
+    ///
+    /// The order is a pseudo-random permutation seeded from `rng`, so the
+    /// iterator needs no memory of the names it has already yielded. It ends
+    /// once every name – as counted by [`cardinality`][`Self::cardinality`] –
+    /// has been yielded, skipping any that are
+    /// [excluded][`Self::with_excluded`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-rng")] {
+    /// let petnames = petname::Petnames::new("fair", "very", "cat dog");
+    /// let namer = petnames.namer(2, "-");
+    /// let mut names: Vec<String> = namer.unique_iter(&mut rand::rng()).collect();
+    /// names.sort();
+    /// assert_eq!(names, ["fair-cat", "fair-dog"]);
+    /// # }
+    /// ```
+    pub fn unique_iter<'b>(&'b self, rng: &mut dyn rand::Rng) -> impl Iterator<Item = String> + 'b {
+        let names = self.names();
+        let permutation = permutation::Permutation::new(names, rng);
+        (0..names)
+            .map(move |index| {
+                let mut buf = String::new();
+                self.generate_at_into(&mut buf, permutation.apply(index));
+                buf
+            })
+            .filter(|name| !self.is_excluded(name))
+    }
+
+    /// Generate a petname deterministically from `key`.
+    ///
+    /// See [`Indexed::name_for_key`]; this also applies the style and suffix,
+    /// if any, given to [`with_style`][`Self::with_style`] and
+    /// [`with_suffix`][`Self::with_suffix`]. Without a suffix, the name is the
+    /// same as [`Indexed::name_for_key`] would give. Excluded names are not
+    /// skipped, since that would change the names of other keys.
+    pub fn name_for_key(&self, key: &[u8]) -> Option<String> {
+        match self.names() {
+            0 => None,
+            cardinality => {
+                let mut buf = String::new();
+                self.generate_at_into(&mut buf, hash::fnv1a_128(key) % cardinality);
+                (!buf.is_empty()).then_some(buf)
+            }
+        }
+    }
+
+    /// Calculate the number of distinct names that this namer can produce,
+    /// counting the suffix, if any, and only names within the maximum length,
+    /// if any, that are not excluded.
+    ///
+    /// This can saturate, as [`Indexed::cardinality`] can.
+    pub fn cardinality(&self) -> u128 {
+        self.names().saturating_sub(self.excluded_names())
+    }
+
+    /// The number of names that this namer can produce, as
+    /// [`cardinality`][`Self::cardinality`] counts them, but counting
+    /// excluded names too.
+    fn names(&self) -> u128 {
+        match self.max_len_cardinality().unwrap_or_else(|| self.generator.cardinality(self.words)) {
+            0 => 0,
+            cardinality => cardinality.saturating_mul(self.suffix.map_or(1, |suffix| suffix.cardinality())),
+        }
+    }
+
+    /// The entropy, in bits, of a name chosen uniformly by this namer,
+    /// counting the suffix, if any. See [`Indexed::entropy_bits`].
+    pub fn entropy_bits(&self) -> f64 {
+        if self.excluded.is_some() {
+            return match self.cardinality() {
+                0 => 0.0,
+                names => log2(names as f64),
+            };
+        }
+        let suffix_bits = self.suffix.map_or(0.0, |suffix| suffix.entropy_bits());
+        match self.max_len_cardinality() {
+            Some(0) => 0.0,
+            Some(names) => log2(names as f64) + suffix_bits,
+            None => match self.generator.cardinality(self.words) {
+                0 => 0.0,
+                _ => self.generator.entropy_bits(self.words) + suffix_bits,
+            },
+        }
+    }
+
+    /// Generate the name at `index`, styled and suffixed, into `buf`. The
+    /// suffix is the least significant part of the index.
+    fn generate_at_into(&self, buf: &mut String, index: u128) {
+        let suffixes = self.suffix.map_or(1, |suffix| suffix.cardinality());
+        let start = buf.len();
+        self.generate_styled(buf, |buf| self.generate_words_at_into(buf, index / suffixes, self.separator));
+        if let Some(suffix) = self.suffix_for(buf, start) {
+            suffix.generate_at_into(buf, index % suffixes);
+        }
+    }
+}
+
 /// Word lists prepared for alliteration.
 ///
 /// Construct from a [`Petnames`] with [`Alliterations::from`]. This takes that
 /// instance and splits it into several _groups_. In each, all of the nouns,
-/// adverbs, and adjectives will start with the same letter. A name generated
-/// from any of them will naturally produce an alliterative petname.
+/// adverbs, and adjectives will start with the same letter, ignoring case and
+/// accents. A name generated from any of them will naturally produce an
+/// alliterative petname. For other rules, like Turkish's, see
+/// [`by_first_letter`][`Self::by_first_letter`].
 ///
 /// You can also create one of these from an iterable of `(char, Petnames)`.
 /// This might be useful for testing, or for repurposing this to generate names
-/// with assonance, say.
+/// with assonance, say. For names that rhyme, see [`Rhymes`].
+///
+/// Groups need not be keyed by a letter. [`group_by`][`Self::group_by`] groups
+/// words by any [`Ord`] key, like their first two letters or their length, and
+/// an iterable of `(K, Petnames)` makes an `Alliterations<K>` too.
+///
+/// By default, names are chosen at random by first choosing a group, then a
+/// name within it, so names from small groups are more likely than those from
+/// large groups. Use [`with_sampling`][`Self::with_sampling`] to make every
+/// name equally likely instead.
 ///
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Alliterations<'a> {
-    groups: BTreeMap<char, Petnames<'a>>,
+pub struct Alliterations<'a, K = char> {
+    groups: BTreeMap<K, Petnames<'a>>,
+    sampling: Sampling,
 }
 
-impl Alliterations<'_> {
+/// How a generator made of groups, like [`Alliterations`] or [`Rhymes`],
+/// chooses names at random.
+///
+/// This does not affect [`Indexed`] methods, like [`Namer::unique_iter`],
+/// which treat every name alike.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Sampling {
+    /// Choose a group, every group being equally likely, then a name within
+    /// it. A name that begins with a rare letter, like `x`, is then far more
+    /// likely than one that begins with a common letter.
+    #[default]
+    ByGroup,
+    /// Every name is equally likely, so each group is chosen in proportion to
+    /// its cardinality for the number of words wanted.
+    Uniform,
+}
+
+impl<'a, K: Ord> Alliterations<'a, K> {
+    /// Iterate over the groups, in order of their key, e.g. their first
+    /// letter.
+    ///
+    /// A _group_ is defined by a key, e.g. a [`char`], and a corresponding
+    /// [`Petnames`] instance; see [`retain`][`Self::retain`].
+    ///
+    pub fn groups(&self) -> impl Iterator<Item = (K, &Petnames<'a>)>
+    where
+        K: Clone,
+    {
+        self.groups.iter().map(|(key, group)| (key.clone(), group))
+    }
+
     /// Keep only those groups that match a predicate.
     ///
-    /// A _group_ is defined by a [`char`] and a corresponding [`Petnames`]
-    /// instance.
+    /// A _group_ is defined by a key, e.g. a [`char`], and a corresponding
+    /// [`Petnames`] instance.
     ///
     /// The given predicate can return `true` to keep the group or `false` to
     /// evict it. It can also mutate each `Petnames` instance. The notional
     /// invariant is that every noun, adverb, and adjective in that `Petnames`
-    /// instance should start with that `char`, but it's okay to break that.
+    /// instance should have that key, e.g. start with that `char`, but it's
+    /// okay to break that.
     ///
     pub fn retain<F>(&mut self, predicate: F)
     where
-        F: FnMut(&char, &mut Petnames) -> bool,
+        F: FnMut(&K, &mut Petnames<'a>) -> bool,
     {
         self.groups.retain(predicate)
     }
@@ -330,60 +922,243 @@ impl Alliterations<'_> {
     ///     .expect("no names");
     /// ```
     pub fn namer<'b>(&'b self, words: u8, separator: &'b str) -> Namer<'b, Self> {
-        Namer { generator: self, words, separator }
+        Namer::new(self, words, separator)
     }
-}
 
-impl<'a> From<Petnames<'a>> for Alliterations<'a> {
-    fn from(petnames: Petnames<'a>) -> Self {
-        let mut adjectives: BTreeMap<char, Vec<&str>> = group_words_by_first_letter(petnames.adjectives);
-        let mut adverbs: BTreeMap<char, Vec<&str>> = group_words_by_first_letter(petnames.adverbs);
-        let nouns: BTreeMap<char, Vec<&str>> = group_words_by_first_letter(petnames.nouns);
-        // We find all adjectives and adverbs that start with the same letter as
-        // each group of nouns. We start from nouns because it's possible to
-        // have a petname with length of 1, i.e. a noun. This means that it's
-        // okay at this point for the adjectives and adverbs lists to be empty.
+    /// Choose names at random as `sampling` says; see [`Sampling`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::{Alliterations, Petnames, Sampling};
+    /// let petnames = Petnames::new("able apt arid bold", "", "ant bee");
+    /// let alliterations = Alliterations::from(petnames).with_sampling(Sampling::Uniform);
+    /// assert_eq!(alliterations.sampling(), Sampling::Uniform);
+    /// ```
+    pub fn with_sampling(self, sampling: Sampling) -> Self {
+        Self { sampling, ..self }
+    }
+
+    /// How names are chosen at random.
+    pub fn sampling(&self) -> Sampling {
+        self.sampling
+    }
+
+    /// Split `petnames` into groups of words with the same key, as given by
+    /// `key` for each word.
+    ///
+    /// As with [`Alliterations::from`], every word in a name generated from
+    /// any group will then have the same key. Each noun is in exactly one
+    /// group, as is each adverb and adjective that has the same key as at
+    /// least one noun.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::{Alliterations, Petnames};
+    /// let petnames = Petnames::new("brave bright calm", "", "bream brown cat");
+    /// let by_first_two: Alliterations<String> =
+    ///     Alliterations::group_by(petnames.clone(), |word| word.chars().take(2).collect());
+    /// let keys: Vec<String> = by_first_two.groups().map(|(key, _)| key).collect();
+    /// assert_eq!(keys, ["br", "ca"]);
+    /// assert_eq!(by_first_two.cardinality(2), 4 + 1);
+    ///
+    /// let by_length = Alliterations::group_by(petnames, |word| word.chars().count());
+    /// assert_eq!(by_length.groups().map(|(key, _)| key).collect::<Vec<_>>(), [3, 5]);
+    /// ```
+    ///
+    pub fn group_by<F>(petnames: Petnames<'a>, mut key: F) -> Self
+    where
+        F: FnMut(&str) -> K,
+    {
+        Self::grouped(petnames, |word| Some(key(word)))
+    }
+
+    /// Split `petnames` into groups of words with the same key. Words without
+    /// a key are left out.
+    fn grouped(petnames: Petnames<'a>, mut key: impl FnMut(&str) -> Option<K>) -> Self {
+        let mut adjectives: BTreeMap<K, Vec<&str>> = group_words_by(petnames.adjectives, &mut key);
+        let mut adverbs: BTreeMap<K, Vec<&str>> = group_words_by(petnames.adverbs, &mut key);
+        let nouns: BTreeMap<K, Vec<&str>> = group_words_by(petnames.nouns, &mut key);
+        // We find all adjectives and adverbs with the same key as each group of
+        // nouns. We start from nouns because it's possible to have a petname
+        // with length of 1, i.e. a noun. This means that it's okay at this
+        // point for the adjectives and adverbs lists to be empty.
         Alliterations {
-            groups: nouns.into_iter().fold(BTreeMap::default(), |mut acc, (first_letter, nouns)| {
-                acc.insert(
-                    first_letter,
-                    Petnames {
-                        adjectives: adjectives.remove(&first_letter).unwrap_or_default().into(),
-                        adverbs: adverbs.remove(&first_letter).unwrap_or_default().into(),
+            groups: nouns
+                .into_iter()
+                .map(|(key, nouns)| {
+                    let group = Petnames {
+                        adjectives: adjectives.remove(&key).unwrap_or_default().into(),
+                        adverbs: adverbs.remove(&key).unwrap_or_default().into(),
                         nouns: Cow::from(nouns),
-                    },
-                );
-                acc
-            }),
+                    };
+                    (key, group)
+                })
+                .collect(),
+            sampling: Sampling::default(),
         }
     }
 }
 
-impl<'a, GROUPS> From<GROUPS> for Alliterations<'a>
+impl<'a> From<Petnames<'a>> for Alliterations<'a> {
+    /// Group words by their first letter exactly as it is written, so `É`,
+    /// `E`, and `e` are in different groups. To fold case and accents, use
+    /// [`Alliterations::by_first_letter`] instead.
+    fn from(petnames: Petnames<'a>) -> Self {
+        Self::grouped(petnames, |word| word.chars().next())
+    }
+}
+
+impl<'a> Alliterations<'a> {
+    /// Split `petnames` into groups of words that begin with the same letter,
+    /// once folded by `folding`. Unlike [`From<Petnames>`][From], this puts
+    /// `Émile`, `E\u{301}mile`, `emerald`, and `Ember` in one group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use petname::{Alliterations, Folding, Petnames};
+    /// let petnames = Petnames::new("Émile emerald", "", "Ember ibis");
+    /// let alliterations = Alliterations::by_first_letter(petnames, Folding::Latin);
+    /// let keys: Vec<char> = alliterations.groups().map(|(key, _)| key).collect();
+    /// assert_eq!(keys, ['e', 'i']);
+    /// assert_eq!(alliterations.cardinality(2), 2);
+    /// ```
+    ///
+    pub fn by_first_letter(petnames: Petnames<'a>, folding: Folding) -> Self {
+        Self::grouped(petnames, |word| folding.first_letter(word))
+    }
+
+    /// Split `petnames` into groups of words that begin with the same sound,
+    /// rather than the same letter.
+    ///
+    /// Each group's key is the [`initial_sound`] of its words: `K` for both
+    /// `cat` and `kitten`, and `N` for both `knowing` and `nightingale`, say,
+    /// whereas `cheerful` has a group of its own, `C`. Words without letters
+    /// are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use petname::{Alliterations, Petnames};
+    /// let petnames = Petnames::new("cheerful knowing", "", "kitten nightingale");
+    /// let alliterations = Alliterations::phonetic(petnames);
+    /// assert_eq!(alliterations.cardinality(2), 1);
+    /// let keys: Vec<char> = alliterations.groups().map(|(key, _)| key).collect();
+    /// assert_eq!(keys, ['K', 'N']);
+    /// ```
+    ///
+    pub fn phonetic(petnames: Petnames<'a>) -> Self {
+        Self::grouped(petnames, initial_sound)
+    }
+}
+
+impl<'a, K, GROUPS> From<GROUPS> for Alliterations<'a, K>
 where
-    GROUPS: IntoIterator<Item = (char, Petnames<'a>)>,
+    K: Ord,
+    GROUPS: IntoIterator<Item = (K, Petnames<'a>)>,
 {
     fn from(groups: GROUPS) -> Self {
-        Self { groups: groups.into_iter().collect() }
+        Self { groups: groups.into_iter().collect(), sampling: Sampling::default() }
     }
 }
 
-fn group_words_by_first_letter(words: Words<'_>) -> BTreeMap<char, Vec<&str>> {
-    words.iter().fold(BTreeMap::default(), |mut acc, s| match s.chars().next() {
-        Some(first_letter) => {
-            acc.entry(first_letter).or_default().push(s);
+fn group_words_by<'a, K: Ord>(
+    words: Words<'a>,
+    key: &mut impl FnMut(&str) -> Option<K>,
+) -> BTreeMap<K, Vec<&'a str>> {
+    words.iter().fold(BTreeMap::default(), |mut acc, s| match key(s) {
+        Some(key) => {
+            acc.entry(key).or_default().push(s);
             acc
         }
         None => acc,
     })
 }
 
-impl Generator for Alliterations<'_> {
+impl<K: Ord> Generator for Alliterations<'_, K> {
     fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
-        if let Some(group) = self.groups.values().choose(rng) {
-            group.generate_into(buf, rng, words, separator);
+        match self.sampling {
+            Sampling::ByGroup => {
+                if let Some(group) = self.groups.values().choose(rng) {
+                    group.generate_into(buf, rng, words, separator);
+                }
+            }
+            Sampling::Uniform => generate_uniformly_into(self, buf, rng, words, separator),
         }
     }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.groups
+            .values()
+            .find_map(|group| group.find_word_ranges(name, words, separator, None))
+            .unwrap_or_else(|| style::split_ranges(name, separator))
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        let finders: Vec<_> =
+            self.groups.values().map(|group| group.word_range_finder(words, separator)).collect();
+        Box::new(move |name| {
+            finders.iter().find_map(|find| find(name)).unwrap_or_else(|| style::split_ranges(name, separator))
+        })
+    }
+}
+
+impl<K: Ord> Indexed for Alliterations<'_, K> {
+    fn cardinality(&self, words: u8) -> u128 {
+        Alliterations::cardinality(self, words)
+    }
+
+    /// Counts names as a float, group by group, which does not saturate.
+    fn entropy_bits(&self, words: u8) -> f64 {
+        if self.cardinality(words) == 0 {
+            return 0.0;
+        }
+        log2(
+            self.groups
+                .values()
+                .map(|group| group.radices(words).into_iter().map(|radix| radix as f64).product::<f64>())
+                .sum(),
+        )
+    }
+
+    /// Groups are laid end to end, in order of their first letter.
+    fn generate_at_into(&self, buf: &mut String, mut index: u128, words: u8, separator: &str) {
+        for group in self.groups.values() {
+            let cardinality = group.cardinality(words);
+            if index < cardinality {
+                group.generate_at_into(buf, index, words, separator);
+                return;
+            }
+            index -= cardinality;
+        }
+    }
+
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        let mut preceding = 0u128;
+        for group in self.groups.values() {
+            match group.index_of(name, words, separator) {
+                Some(index) => return index.checked_add(preceding),
+                None => preceding = preceding.checked_add(group.cardinality(words))?,
+            }
+        }
+        None
+    }
+
+    /// A name can be split differently in different groups, e.g. `x-ray-fish`
+    /// as `x-ray` + `fish` and as `x` + `ray-fish` when grouped by length.
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        let mut preceding = 0u128;
+        let mut indices = alloc::vec![Vec::new(); names.len()];
+        for group in self.groups.values() {
+            for (indices, found) in indices.iter_mut().zip(group.indices_of(names, words, separator)) {
+                indices.extend(found.into_iter().filter_map(|index| index.checked_add(preceding)));
+            }
+            preceding = preceding.saturating_add(group.cardinality(words));
+        }
+        indices
+    }
 }
 
 #[cfg(feature = "default-words")]
@@ -394,8 +1169,141 @@ impl Default for Alliterations<'_> {
     }
 }
 
+/// Generate a name chosen uniformly from all of `generator`'s names, for
+/// [`Sampling::Uniform`]. Nothing is pushed if there are no names.
+fn generate_uniformly_into<G: Indexed>(
+    generator: &G,
+    buf: &mut String,
+    rng: &mut dyn rand::Rng,
+    words: u8,
+    separator: &str,
+) {
+    let cardinality = generator.cardinality(words);
+    if cardinality > 0 {
+        generator.generate_at_into(buf, rng.random_range(0..cardinality), words, separator);
+    }
+}
+
+/// Split `index` into one digit per radix, most significant first.
+///
+/// This treats `index` as a mixed-radix number in which the last radix varies
+/// fastest, i.e. the last word of a name is the least significant. Every radix
+/// must be non-zero.
+fn mixed_radix(mut index: u128, radices: &[usize]) -> Vec<usize> {
+    let mut digits = alloc::vec![0; radices.len()];
+    for (digit, &radix) in digits.iter_mut().zip(radices).rev() {
+        let radix = radix as u128;
+        *digit = (index % radix) as usize;
+        index /= radix;
+    }
+    digits
+}
+
+/// Combine digits, most significant first, into an index.
+///
+/// This is the inverse of [`mixed_radix`]. Returns `None` if the index does not
+/// fit in a `u128`.
+fn mixed_radix_index(digits: &[usize], radices: &[usize]) -> Option<u128> {
+    digits.iter().zip(radices).try_fold(0u128, |index, (&digit, &radix)| {
+        index.checked_mul(radix as u128)?.checked_add(digit as u128)
+    })
+}
+
+/// The base-2 logarithm of `x`, which must be positive and finite.
+///
+/// [`f64::log2`] needs `std`, so this takes the exponent from the bits of `x`
+/// and finds the fraction by repeatedly squaring the mantissa.
+fn log2(x: f64) -> f64 {
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    // The mantissa, scaled into [1, 2).
+    let mut mantissa = f64::from_bits(bits & ((1 << 52) - 1) | 1023 << 52);
+    let (mut log, mut bit) = (exponent as f64, 1.0);
+    for _ in 0..f64::MANTISSA_DIGITS {
+        mantissa *= mantissa;
+        bit /= 2.0;
+        if mantissa >= 2.0 {
+            mantissa /= 2.0;
+            log += bit;
+        }
+    }
+    log
+}
+
+/// A function that finds a word in a word list, returning its position.
+type Lookup<'f> = &'f dyn Fn(&str) -> Option<usize>;
+
+/// The position of each word in `list`, for finding many words quickly. Where
+/// a word appears more than once, its first position is kept, as
+/// [`Iterator::position`] would find it.
+fn positions<'w>(list: &[&'w str]) -> BTreeMap<&'w str, usize> {
+    let mut positions = BTreeMap::new();
+    for (position, &word) in list.iter().enumerate() {
+        positions.entry(word).or_insert(position);
+    }
+    positions
+}
+
+/// Find up to `limit` ways to split `name` into words, one from each list in
+/// turn, joined by `separator`.
+///
+/// Each split is the position of each word in its list. Splits with shorter
+/// leading words are found first.
+fn splits(name: &str, separator: &str, lists: &[Lookup], limit: usize) -> Vec<Vec<usize>> {
+    fn split(
+        name: &str,
+        separator: &str,
+        lists: &[Lookup],
+        digits: &mut Vec<usize>,
+        found: &mut Vec<Vec<usize>>,
+        limit: usize,
+    ) {
+        match lists {
+            [] => {
+                if name.is_empty() {
+                    found.push(digits.clone());
+                }
+            }
+            [list] => {
+                if let Some(digit) = list(name) {
+                    digits.push(digit);
+                    found.push(digits.clone());
+                    digits.pop();
+                }
+            }
+            [list, rest @ ..] => {
+                let ends: Vec<usize> = if separator.is_empty() {
+                    name.char_indices().skip(1).map(|(end, _)| end).collect()
+                } else {
+                    name.match_indices(separator).map(|(end, _)| end).collect()
+                };
+                for end in ends {
+                    if found.len() >= limit {
+                        break;
+                    }
+                    if let Some(digit) = list(&name[..end]) {
+                        digits.push(digit);
+                        split(&name[end + separator.len()..], separator, rest, digits, found, limit);
+                        digits.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    split(name, separator, lists, &mut Vec::with_capacity(lists.len()), &mut found, limit);
+    found
+}
+
+/// The most words that `name` could be split into with `separator`.
+fn max_words(name: &str, separator: &str) -> u8 {
+    let max = if separator.is_empty() { name.chars().count() } else { name.matches(separator).count() + 1 };
+    max.try_into().unwrap_or(u8::MAX)
+}
+
 /// Enum representing which word list to use.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum List {
     Adverb,
     Adjective,
@@ -470,8 +1378,57 @@ impl Iterator for Lists {
     }
 }
 
+/// Iterator, yielding the slot to fill for each word of a name.
+///
+/// A grammar has some slots, of which one may repeat. A name of `n` words uses
+/// the last `n` slots or, if there are more words than slots, every slot, with
+/// the one that repeats filling the difference.
+#[derive(Clone, Debug)]
+struct Slots<'s, T> {
+    /// The slots yet to be yielded.
+    slots: &'s [T],
+    /// The position in `slots` of the slot that repeats, if it is needed.
+    repeat: Option<usize>,
+    /// How many more times that slot repeats.
+    repeats: usize,
+}
+
+impl<'s, T> Slots<'s, T> {
+    /// The slots for a name of `words` words, if there can be one.
+    fn new(slots: &'s [T], repeat: Option<usize>, words: u8) -> Option<Self> {
+        match usize::from(words).checked_sub(slots.len()) {
+            None | Some(0) => {
+                Some(Self { slots: &slots[slots.len() - usize::from(words)..], repeat: None, repeats: 0 })
+            }
+            Some(repeats) => Some(Self { slots, repeat: Some(repeat?), repeats }),
+        }
+    }
+}
+
+impl<'s, T> Iterator for Slots<'s, T> {
+    type Item = &'s T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (slot, rest) = self.slots.split_first()?;
+        if self.repeat == Some(0) && self.repeats > 0 {
+            self.repeats -= 1;
+        } else {
+            self.slots = rest;
+            self.repeat = self.repeat.and_then(|repeat| repeat.checked_sub(1));
+        }
+        Some(slot)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.slots.len() + self.repeats;
+        (remaining, Some(remaining))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
+
     #[test]
     fn lists_sequences_adverbs_adjectives_then_names() {
         let mut lists = super::Lists::new(4);
@@ -487,6 +1444,24 @@ mod tests {
         assert_eq!(None, lists.next());
     }
 
+    #[test]
+    fn slots_fill_the_last_slots_then_repeat() {
+        use super::List::{Adjective, Adverb, Noun};
+        let slots = [Adverb, Adjective, Noun];
+        let fill =
+            |repeat, words| Some(super::Slots::new(&slots, repeat, words)?.copied().collect::<Vec<_>>());
+        assert_eq!(fill(Some(0), 0), Some(alloc::vec![]));
+        assert_eq!(fill(Some(0), 1), Some(alloc::vec![Noun]));
+        assert_eq!(fill(Some(0), 2), Some(alloc::vec![Adjective, Noun]));
+        assert_eq!(fill(Some(0), 4), Some(alloc::vec![Adverb, Adverb, Adjective, Noun]));
+        assert_eq!(fill(Some(1), 4), Some(alloc::vec![Adverb, Adjective, Adjective, Noun]));
+        assert_eq!(fill(None, 3), Some(alloc::vec![Adverb, Adjective, Noun]));
+        assert_eq!(fill(None, 4), None);
+        for words in 0..=6 {
+            assert_eq!(fill(Some(0), words), Some(super::Lists::new(words).collect()));
+        }
+    }
+
     #[test]
     fn lists_size_hint() {
         let mut lists = super::Lists::new(3);
@@ -500,4 +1475,13 @@ mod tests {
         assert_eq!(None, lists.next());
         assert_eq!((0, Some(0)), lists.size_hint());
     }
+
+    #[test]
+    fn log2_is_accurate() {
+        assert_eq!(super::log2(1.0), 0.0);
+        assert_eq!(super::log2(1024.0), 10.0);
+        assert_eq!(super::log2(0.5), -1.0);
+        assert!((super::log2(3.0) - 1.584_962_500_721_156).abs() < 1e-12);
+        assert!((super::log2(u128::MAX as f64) - 128.0).abs() < 1e-12);
+    }
 }
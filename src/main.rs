@@ -1,12 +1,17 @@
 mod cli;
+mod stats;
 
 use cli::Cli;
 use petname::Alliterations;
-use petname::{Generator, Namer, Petnames};
+use petname::{
+    Blocking, Blocklist, Generator, Indexed, Lengths, Namer, Petnames, Profiled, Rhymes, Template, Weighted,
+};
+
+use std::collections::HashSet;
 
 use std::fmt;
 use std::fs;
-use std::io;
+use std::io::{self, BufRead};
 use std::path;
 use std::process;
 
@@ -42,7 +47,14 @@ enum Error {
     Randomness(String),
     Cardinality(String),
     Alliteration(String),
-    #[cfg(feature = "lang-turkish")]
+    Invalid(String),
+    Encoding(String),
+    Entropy(String),
+    Template(String),
+    Weights(String),
+    Blocklist(String),
+    Batch(String),
+    Profile(String),
     Unsupported(String),
     Disconnected,
 }
@@ -55,7 +67,14 @@ impl fmt::Display for Error {
             Error::Randomness(ref message) => write!(f, "no source of randomness: {message}"),
             Error::Cardinality(ref message) => write!(f, "cardinality is zero: {message}"),
             Error::Alliteration(ref message) => write!(f, "cannot alliterate: {message}"),
-            #[cfg(feature = "lang-turkish")]
+            Error::Invalid(ref message) => write!(f, "invalid names: {message}"),
+            Error::Encoding(ref message) => write!(f, "cannot encode or decode: {message}"),
+            Error::Entropy(ref message) => write!(f, "not enough entropy: {message}"),
+            Error::Template(ref message) => write!(f, "invalid template: {message}"),
+            Error::Weights(ref message) => write!(f, "invalid weights: {message}"),
+            Error::Blocklist(ref message) => write!(f, "invalid blocklist: {message}"),
+            Error::Batch(ref message) => write!(f, "names are too similar: {message}"),
+            Error::Profile(ref message) => write!(f, "cannot keep to profile: {message}"),
             Error::Unsupported(ref message) => write!(f, "unsupported: {message}"),
             Error::Disconnected => write!(f, "caller disconnected / stopped reading"),
         }
@@ -79,7 +98,9 @@ where
             clap_complete::generate(shell, &mut command, "petname", writer);
             Ok(())
         }
-        None => run_default(cli, writer),
+        Some(cli::Command::Check { words, ref names }) => run_check(&cli, words, names, writer),
+        Some(cli::Command::Encode { .. } | cli::Command::Decode { .. } | cli::Command::Stats { .. })
+        | None => run_default(cli, writer),
     }
 }
 
@@ -98,6 +119,16 @@ where
     // Stream, or print a limited number of words?
     let count = if cli.stream { None } else { Some(cli.count) };
 
+    // Names are encoded and decoded as the generator produces them, without
+    // a style, so a style would be ignored.
+    if cli.style().is_some()
+        && matches!(cli.command, Some(cli::Command::Encode { .. } | cli::Command::Decode { .. }))
+    {
+        return Err(Error::Unsupported(
+            "--case and --profile are not supported by encode or decode".to_string(),
+        ));
+    }
+
     // Non-English languages use their own generators.
     match cli.language {
         cli::Language::English => run_english(&cli, writer, &mut rng, count),
@@ -116,54 +147,464 @@ where
     OUT: io::Write,
     RNG: rand::Rng,
 {
-    // Load custom word lists, if specified.
-    let words = match cli.directory {
-        Some(ref dirname) => Words::load(dirname)?,
-        None => Words::Builtin,
-    };
+    let custom = Words::for_cli(cli)?;
+    let blocklist = blocklist_for(cli)?;
 
-    // Select the appropriate word list.
-    let mut petnames = match words {
-        Words::Custom(ref adjectives, ref adverbs, ref nouns) => Petnames::new(adjectives, adverbs, nouns),
-        Words::Builtin => match cli.lists {
-            cli::WordList::Small => Petnames::small(),
-            cli::WordList::Medium => Petnames::medium(),
-            cli::WordList::Large => Petnames::large(),
-        },
-    };
+    // Weighted names are not indexed, so they are generated separately.
+    if cli.prefer_common {
+        return run_weighted(cli, &custom, &blocklist, writer, rng, count);
+    }
 
-    // If requested, limit the number of letters.
-    let letters = cli.letters;
-    if letters != 0 {
-        petnames.retain(|s| s.len() <= letters);
+    let (petnames, removed) = english_petnames(cli, &custom, &blocklist)?;
+
+    // A template decides the shape of names by itself, so --words and
+    // --separator conflict with --template, and it takes no number of words.
+    if let Some(ref pattern) = cli.template {
+        let template = Template::new(&petnames, pattern).map_err(|err| Error::Template(err.to_string()))?;
+        if template.cardinality() == 0 {
+            return Err(Error::Cardinality(
+                "no petnames to choose from; try relaxing constraints".to_string(),
+            ));
+        }
+        return emit(cli, writer, &template, 0, removed, rng, count);
     }
 
+    // Only some generators can count their names by length.
+    if let Some(max_len) = cli.max_len() {
+        return if cli.alliterate.is_some() || cli.ubuntu || cli.alliterate_with.is_some() {
+            let mut alliterations = alliterations_for(cli, petnames, &blocklist);
+            if let Some(alliterate_with) = cli.alliteration_letter() {
+                alliterations.retain(|first_letter, _| *first_letter == alliterate_with);
+            }
+            emit_within(cli, writer, &alliterations, max_len, None, rng, count)
+        } else if cli.rhyme {
+            let rhymes = remove_blocked(cli, Rhymes::group_by(petnames, petname::rhyme_key), &blocklist);
+            emit_within(cli, writer, &rhymes, max_len, None, rng, count)
+        } else if let Some(group_by) = cli.group_by {
+            let groups = remove_blocked(cli, group_by.group(petnames), &blocklist);
+            emit_within(cli, writer, &groups, max_len, None, rng, count)
+        } else if derives_names(cli) {
+            // As without --max-length, skipping blocked pairs would change
+            // every name derived from a key.
+            emit_within(cli, writer, &petnames, max_len, Some((&petnames, &blocklist)), rng, count)
+        } else {
+            let blocking = Blocking::new(&petnames, &blocklist, cli.separator());
+            emit_within(cli, writer, &blocking, max_len, None, rng, count)
+        };
+    }
+
+    let words = words_for(cli, &petnames)?;
+
     // Check cardinality.
-    if petnames.cardinality(cli.words) == 0 {
+    if petnames.cardinality(words) == 0 {
         return Err(Error::Cardinality("no petnames to choose from; try relaxing constraints".to_string()));
     }
 
     // Get an iterator for the names we want to print out, handling alliteration.
-    if cli.alliterate || cli.ubuntu {
-        let mut alliterations: Alliterations = petnames.into();
-        alliterations.retain(|_, group| group.cardinality(cli.words) > 0);
-        if alliterations.cardinality(cli.words) == 0 {
+    if let Some(alliterate_with) = cli.alliteration_letter() {
+        let mut alliterations = alliterations_for(cli, petnames, &blocklist);
+        alliterations.retain(|first_letter, _| *first_letter == alliterate_with);
+        let words = words_for(cli, &alliterations)?;
+        if alliterations.cardinality(words) == 0 {
+            return Err(Error::Alliteration(
+                "no petnames begin with the chosen alliteration character".to_string(),
+            ));
+        }
+        emit(cli, writer, &alliterations, words, removed, rng, count)
+    } else if cli.alliterate.is_some() || cli.ubuntu {
+        let mut alliterations = alliterations_for(cli, petnames, &blocklist);
+        let words = words_for(cli, &alliterations)?;
+        alliterations.retain(|_, group| group.cardinality(words) > 0);
+        if alliterations.cardinality(words) == 0 {
             return Err(Error::Alliteration("word lists have no initial letters in common".to_string()));
         }
-        printer(writer, &alliterations.namer(cli.words, &cli.separator), rng, count)
-    } else if let Some(alliterate_with) = cli.alliterate_with {
-        let mut alliterations: Alliterations = petnames.into();
-        alliterations.retain(|first_letter, group| {
-            *first_letter == alliterate_with && group.cardinality(cli.words) > 0
+        emit(cli, writer, &alliterations, words, removed, rng, count)
+    } else if cli.rhyme {
+        let mut rhymes = remove_blocked(cli, Rhymes::group_by(petnames, petname::rhyme_key), &blocklist)
+            .with_sampling(cli.sampling());
+        let words = words_for(cli, &rhymes)?;
+        rhymes.retain(|_, group| group.cardinality(words) > 0);
+        if rhymes.cardinality(words) == 0 {
+            return Err(Error::Cardinality("word lists have no rhymes in common".to_string()));
+        }
+        emit(cli, writer, &rhymes, words, removed, rng, count)
+    } else if let Some(group_by) = cli.group_by {
+        let mut groups =
+            remove_blocked(cli, group_by.group(petnames), &blocklist).with_sampling(cli.sampling());
+        let words = words_for(cli, &groups)?;
+        groups.retain(|_, group| group.cardinality(words) > 0);
+        if groups.cardinality(words) == 0 {
+            return Err(Error::Cardinality("no words have the same key for --group-by".to_string()));
+        }
+        emit(cli, writer, &groups, words, removed, rng, count)
+    } else if cli.distinct_words {
+        let distinct = petnames.distinct();
+        let words = words_for(cli, &distinct)?;
+        if distinct.cardinality(words) == 0 {
+            return Err(Error::Cardinality("not enough words to avoid repeating any".to_string()));
+        }
+        emit(cli, writer, &distinct, words, removed, rng, count)
+    } else if derives_names(cli) {
+        // Skipping blocked pairs would change every name derived from a key,
+        // and every encoding, so blocked names are warned of instead.
+        let blocked = BlockedNames::new(cli, &petnames, &blocklist, words, None);
+        emit_derived(cli, writer, &petnames, words, &blocked)
+    } else {
+        let blocking = Blocking::new(&petnames, &blocklist, cli.separator());
+        let words = words_for(cli, &blocking)?;
+        if blocking.cardinality(words) == 0 {
+            return Err(Error::Cardinality("every name is blocked".to_string()));
+        }
+        emit(cli, writer, &blocking, words, removed, rng, count)
+    }
+}
+
+/// Group words for alliteration: by first letter or, with --alliterate=sound,
+/// by first sound.
+fn alliterations_for<'a>(cli: &Cli, petnames: Petnames<'a>, blocklist: &Blocklist) -> Alliterations<'a> {
+    let alliterations = match cli.alliterate {
+        Some(cli::Alliterate::Sound) => Alliterations::phonetic(petnames),
+        Some(cli::Alliterate::Letter) | None => {
+            Alliterations::by_first_letter(petnames, petname::Folding::default())
+        }
+    };
+    remove_blocked(cli, alliterations.with_sampling(cli.sampling()), blocklist)
+}
+
+/// Whether names are derived from keys or bytes, and so must never change.
+fn derives_names(cli: &Cli) -> bool {
+    cli.from_key.is_some()
+        || cli.keys_from_stdin
+        || matches!(cli.command, Some(cli::Command::Encode { .. } | cli::Command::Decode { .. }))
+}
+
+/// Remove words from each group so that no name has a blocked pair of words:
+/// unlike [`Blocking`], grouped names cannot skip only the names with one.
+/// Names derived from keys or bytes must never change, so keep every word.
+fn remove_blocked<'a, K: Ord>(
+    cli: &Cli,
+    mut groups: Alliterations<'a, K>,
+    blocklist: &Blocklist,
+) -> Alliterations<'a, K> {
+    if !derives_names(cli) {
+        groups.retain(|_, group| {
+            group.remove_blocked(blocklist, cli.separator());
+            true
         });
-        if alliterations.cardinality(cli.words) == 0 {
-            return Err(Error::Alliteration(
-                "no petnames begin with the chosen alliteration character".to_string(),
+    }
+    groups
+}
+
+/// Print names of at most `max_len` characters, for --max-length: derived
+/// from keys if any were given, otherwise chosen at random. Names derived
+/// from keys are checked against the blocklist, if given, as with
+/// [`emit_derived`].
+fn emit_within<OUT, GEN, RNG>(
+    cli: &Cli,
+    writer: &mut OUT,
+    generator: &GEN,
+    max_len: usize,
+    check: Option<(&Petnames, &Blocklist)>,
+    rng: &mut RNG,
+    count: Option<usize>,
+) -> Result<(), Error>
+where
+    OUT: io::Write,
+    GEN: Lengths,
+    RNG: rand::Rng,
+{
+    if cli.command.is_some() {
+        return Err(Error::Unsupported(
+            "--max-length is not supported with encode, decode, or stats".to_string(),
+        ));
+    }
+
+    let within = |words| namer(cli, generator, words).with_max_len(max_len);
+    let words = match cli.min_entropy {
+        None => cli.words,
+        // Count only the names that fit, and the suffix.
+        Some(min_bits) => (1..=u8::MAX)
+            .find(|&words| {
+                let namer = within(words);
+                namer.cardinality() > 0 && namer.entropy_bits() >= min_bits
+            })
+            .ok_or_else(|| {
+                Error::Entropy(format!(
+                    "no number of words gives {min_bits} bits per name in {max_len} characters"
+                ))
+            })?,
+    };
+
+    let namer = within(words);
+    if namer.cardinality() == 0 {
+        return Err(Error::Cardinality(format!("no names fit in {max_len} characters")));
+    }
+    let namer = excluding(cli, namer)?;
+
+    let blocked =
+        check.map(|(petnames, blocklist)| BlockedNames::new(cli, petnames, blocklist, words, Some(max_len)));
+    if let Some(ref key) = cli.from_key {
+        keyed_printer(writer, &namer, [Ok(key.clone())], blocked.as_ref())
+    } else if cli.keys_from_stdin {
+        keyed_printer(writer, &namer, io::stdin().lock().lines(), blocked.as_ref())
+    } else {
+        printer(cli, writer, &namer, rng, count)
+    }
+}
+
+/// Generate names that prefer common words, for --prefer-common.
+fn run_weighted<OUT, RNG>(
+    cli: &Cli,
+    custom: &Words,
+    blocklist: &Blocklist,
+    writer: &mut OUT,
+    rng: &mut RNG,
+    count: Option<usize>,
+) -> Result<(), Error>
+where
+    OUT: io::Write,
+    RNG: rand::Rng,
+{
+    let weighted = english_weighted(cli, custom, blocklist)?;
+    match cli.command {
+        Some(cli::Command::Stats { max_words, json }) => {
+            stats::write_weighted(writer, &weighted, max_words, cli.suffix, json)
+                .map_err(suppress_disconnect)?;
+            return writer.flush().map_err(suppress_disconnect);
+        }
+        Some(_) => {
+            return Err(Error::Unsupported(
+                "--prefer-common is not supported with encode or decode".to_string(),
             ));
         }
-        printer(writer, &alliterations.namer(cli.words, &cli.separator), rng, count)
+        None => {}
+    }
+
+    let words = match cli.min_entropy {
+        None => cli.words,
+        // Names are not equally likely, so count their effective entropy.
+        Some(min_bits) => {
+            let suffix_bits = cli.suffix.map_or(0.0, |suffix| suffix.entropy_bits());
+            (1..=u8::MAX)
+                .find(|&words| weighted.entropy_bits(words) + suffix_bits >= min_bits)
+                .ok_or_else(|| Error::Entropy(format!("no number of words gives {min_bits} bits per name")))?
+        }
+    };
+
+    // Check cardinality.
+    if weighted.petnames().cardinality(words) == 0 {
+        return Err(Error::Cardinality("no petnames to choose from; try relaxing constraints".to_string()));
+    }
+
+    let namer = namer(cli, &weighted, words);
+    printer(cli, writer, &namer, rng, count)
+}
+
+/// Select the English word lists, and filter them, as the command-line options
+/// say. Also returns the number of words removed by --phonetic-safe, if given.
+fn english_petnames<'a>(
+    cli: &Cli,
+    words: &'a Words,
+    blocklist: &Blocklist,
+) -> Result<(Petnames<'a>, Option<usize>), Error> {
+    // Select the appropriate word list.
+    let mut petnames = match words {
+        // Words may carry weights, which only --prefer-common uses.
+        Words::Custom(ref adjectives, ref adverbs, ref nouns) => Weighted::new(adjectives, adverbs, nouns)
+            .map_err(|err| Error::Weights(err.to_string()))?
+            .petnames()
+            .clone(),
+        Words::Builtin => builtin_petnames(cli.lists),
+    };
+
+    // If requested, limit the number of letters.
+    if let Some(letters) = cli.letters_range() {
+        petnames.retain_letters(letters);
+    }
+
+    petnames.retain(|s| !blocklist.blocks_word(s));
+
+    // A profile keeps only the words valid where names are to be used.
+    if let Some(cli::Profile(profile)) = cli.profile {
+        if cli.suffix.is_some_and(|suffix| !profile.allows_suffix(suffix)) {
+            return Err(Error::Profile(format!(
+                "a suffix with letters is not valid with the {profile} profile"
+            )));
+        }
+        let profiled = Profiled::new(&petnames, profile).map_err(|err| Error::Profile(err.to_string()))?;
+        petnames = profiled.petnames().clone();
+    }
+
+    // Words are removed last so that a blocked or overlong word never displaces
+    // one that sounds like it.
+    let removed = cli.phonetic_safe.then(|| petnames.dedupe_phonetically());
+
+    Ok((petnames, removed))
+}
+
+/// Select the English word lists, weight them, and filter them, as the
+/// command-line options say.
+fn english_weighted<'a>(cli: &Cli, words: &'a Words, blocklist: &Blocklist) -> Result<Weighted<'a>, Error> {
+    let weighted = match words {
+        Words::Custom(ref adjectives, ref adverbs, ref nouns) => Weighted::new(adjectives, adverbs, nouns),
+        Words::Builtin => Weighted::from_fn(builtin_petnames(cli.lists), commonness()),
+    };
+    let mut weighted = weighted.map_err(|err| Error::Weights(err.to_string()))?;
+
+    // If requested, limit the number of letters.
+    if let Some(letters) = cli.letters_range() {
+        weighted.retain(|s| letters.contains(&s.chars().count()));
+    }
+
+    weighted.retain(|s| !blocklist.blocks_word(s));
+
+    if cli.phonetic_safe {
+        weighted.dedupe_phonetically();
+    }
+
+    Ok(weighted)
+}
+
+/// The blocklist: the built-in one unless --no-default-blocklist, and the one
+/// given by --blocklist.
+fn blocklist_for(cli: &Cli) -> Result<Blocklist, Error> {
+    let mut blocklist = if cli.no_default_blocklist { Blocklist::new() } else { Blocklist::builtin() };
+    if let Some(ref path) = cli.blocklist {
+        let text = read_file_to_string(path)?;
+        let extra =
+            Blocklist::parse(&text).map_err(|err| Error::Blocklist(format!("{err}: {}", path.display())))?;
+        blocklist.extend(extra);
+    }
+    Ok(blocklist)
+}
+
+fn builtin_petnames<'a>(lists: cli::WordList) -> Petnames<'a> {
+    match lists {
+        cli::WordList::Small => Petnames::small(),
+        cli::WordList::Medium => Petnames::medium(),
+        cli::WordList::Large => Petnames::large(),
+    }
+}
+
+/// Weigh how common each built-in word is. No frequency data is bundled, but
+/// the built-in lists are curated to be progressively larger and less
+/// familiar, so a word in the small list has a weight of 4, a word otherwise
+/// in the medium list has a weight of 2, and any other word has a weight of 1.
+/// Every word in the small list has the same weight, then.
+fn commonness() -> impl FnMut(&str) -> f64 {
+    fn all(petnames: Petnames<'static>) -> HashSet<&'static str> {
+        [petnames.adjectives, petnames.adverbs, petnames.nouns]
+            .iter()
+            .flat_map(|words| words.iter().copied())
+            .collect()
+    }
+    let (small, medium) = (all(Petnames::small()), all(Petnames::medium()));
+    move |word| {
+        if small.contains(word) {
+            4.0
+        } else if medium.contains(word) {
+            2.0
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Check that each of the given names could have been generated from the
+/// English word lists selected by the command-line options.
+fn run_check<OUT>(cli: &Cli, words: Option<u8>, names: &[String], writer: &mut OUT) -> Result<(), Error>
+where
+    OUT: io::Write,
+{
+    #[cfg(feature = "lang-turkish")]
+    if cli.language != cli::Language::English {
+        return Err(Error::Unsupported("check is not supported with --language turkish".to_string()));
+    }
+
+    let custom = Words::for_cli(cli)?;
+    let blocklist = blocklist_for(cli)?;
+    // With --prefer-common, words are filtered as they are for weighted names.
+    let petnames = if cli.prefer_common {
+        english_weighted(cli, &custom, &blocklist)?.petnames().clone()
     } else {
-        printer(writer, &petnames.namer(cli.words, &cli.separator), rng, count)
+        english_petnames(cli, &custom, &blocklist)?.0
+    };
+    let alliterate = cli.alliterate.is_some() || cli.ubuntu || cli.alliterate_with.is_some();
+    let initial = |word: &str| match cli.alliterate {
+        Some(cli::Alliterate::Sound) => petname::initial_sound(word),
+        _ => petname::Folding::default().first_letter(word),
+    };
+    let distinct = cli.distinct_words.then(|| petnames.distinct());
+
+    let mut invalid = 0;
+    for name in names {
+        let verdict = match petnames.parse(name, cli.separator()) {
+            Err(err) => Err(err.to_string()),
+            Ok(parsed) => match words {
+                Some(words) if parsed.words() != usize::from(words) => {
+                    Err(format!("expected {words} words, found {}", parsed.words()))
+                }
+                _ if is_blocked(&petnames, &blocklist, name, cli.separator()) => {
+                    Err("is blocked".to_string())
+                }
+                _ if distinct.as_ref().is_some_and(|distinct| {
+                    distinct.index_of(name, parsed.words() as u8, cli.separator()).is_none()
+                }) =>
+                {
+                    Err("repeats a word".to_string())
+                }
+                _ if alliterate => {
+                    let mut initials = parsed
+                        .adverbs
+                        .iter()
+                        .chain(&parsed.adjective)
+                        .chain([&parsed.noun])
+                        .map(|word| initial(word.word));
+                    let first = cli.alliteration_letter().or(initial(parsed.noun.word));
+                    if initials.all(|initial| initial == first) {
+                        Ok(())
+                    } else {
+                        Err("does not alliterate".to_string())
+                    }
+                }
+                _ if cli.group_by.is_some_and(|group_by| {
+                    let key = group_by.key(parsed.noun.word);
+                    !parsed.adverbs.iter().chain(&parsed.adjective).all(|word| group_by.key(word.word) == key)
+                }) =>
+                {
+                    Err("does not share a group".to_string())
+                }
+                _ if cli.rhyme => {
+                    let mut keys = parsed
+                        .adverbs
+                        .iter()
+                        .chain(&parsed.adjective)
+                        .chain([&parsed.noun])
+                        .map(|word| petname::rhyme_key(word.word));
+                    let first = petname::rhyme_key(parsed.noun.word);
+                    if keys.all(|key| key == first) {
+                        Ok(())
+                    } else {
+                        Err("does not rhyme".to_string())
+                    }
+                }
+                _ => Ok(()),
+            },
+        };
+        match verdict {
+            Ok(()) => writeln!(writer, "{name}: ok").map_err(suppress_disconnect)?,
+            Err(reason) => {
+                invalid += 1;
+                writeln!(writer, "{name}: {reason}").map_err(suppress_disconnect)?;
+            }
+        }
+    }
+
+    writer.flush().map_err(suppress_disconnect)?;
+
+    match invalid {
+        0 => Ok(()),
+        invalid => Err(Error::Invalid(format!("{invalid} of {} names are not valid", names.len()))),
     }
 }
 
@@ -182,27 +623,255 @@ where
     if cli.directory.is_some() {
         return Err(Error::Unsupported("--dir is not supported with --language turkish".to_string()));
     }
-    if cli.alliterate || cli.ubuntu || cli.alliterate_with.is_some() {
+    if cli.alliterate.is_some() || cli.ubuntu || cli.alliterate_with.is_some() {
         return Err(Error::Unsupported("alliteration is not supported with --language turkish".to_string()));
     }
+    if cli.rhyme {
+        return Err(Error::Unsupported("--rhyme is not supported with --language turkish".to_string()));
+    }
+    if cli.group_by.is_some() {
+        return Err(Error::Unsupported("--group-by is not supported with --language turkish".to_string()));
+    }
+    if cli.distinct_words {
+        return Err(Error::Unsupported(
+            "--distinct-words is not supported with --language turkish".to_string(),
+        ));
+    }
+    if cli.template.is_some() {
+        return Err(Error::Unsupported("--template is not supported with --language turkish".to_string()));
+    }
+    if cli.prefer_common {
+        return Err(Error::Unsupported(
+            "--prefer-common is not supported with --language turkish".to_string(),
+        ));
+    }
+    if cli.phonetic_safe {
+        return Err(Error::Unsupported(
+            "--phonetic-safe is not supported with --language turkish".to_string(),
+        ));
+    }
+    if cli.max_length.is_some() {
+        return Err(Error::Unsupported("--max-length is not supported with --language turkish".to_string()));
+    }
+    if cli.profile.is_some() {
+        return Err(Error::Unsupported("--profile is not supported with --language turkish".to_string()));
+    }
 
     let mut turkish = petname::lang::turkish::Petnames::small();
 
-    // If requested, limit the number of letters. Count characters, not bytes,
-    // since Turkish words contain multi-byte code points.
-    if cli.letters != 0 {
-        turkish.retain(|s| s.chars().count() <= cli.letters);
+    // If requested, limit the number of letters.
+    if let Some(letters) = cli.letters_range() {
+        turkish.retain_letters(letters);
     }
 
     // Check cardinality.
-    if turkish.cardinality(cli.words) == 0 {
+    let words = words_for(cli, &turkish)?;
+    if turkish.cardinality(words) == 0 {
         return Err(Error::Cardinality("no petnames to choose from; try relaxing constraints".to_string()));
     }
 
-    printer(writer, &turkish.namer(cli.words, &cli.separator), rng, count)
+    emit(cli, writer, &turkish, words, None, rng, count)
+}
+
+/// The number of words in each name: as given by --words, or the fewest that
+/// give at least --min-entropy bits of entropy with the given generator,
+/// counting the bits that --suffix adds.
+fn words_for<GEN: Indexed>(cli: &Cli, generator: &GEN) -> Result<u8, Error> {
+    match cli.min_entropy {
+        None => Ok(cli.words),
+        Some(min_bits) => generator
+            .namer_for_entropy(
+                min_bits - cli.suffix.map_or(0.0, |suffix| suffix.entropy_bits()),
+                cli.separator(),
+            )
+            .map(|namer| namer.words())
+            .ok_or_else(|| Error::Entropy(format!("no number of words gives {min_bits} bits per name"))),
+    }
+}
+
+/// Print names from the given generator: derived from keys if any were given,
+/// otherwise chosen at random.
+fn emit<OUT, GEN, RNG>(
+    cli: &Cli,
+    writer: &mut OUT,
+    generator: &GEN,
+    words: u8,
+    removed: Option<usize>,
+    rng: &mut RNG,
+    count: Option<usize>,
+) -> Result<(), Error>
+where
+    OUT: io::Write,
+    GEN: stats::Describe,
+    RNG: rand::Rng,
+{
+    let namer = namer(cli, generator, words);
+    match cli.command {
+        Some(cli::Command::Encode { raw }) => encoder(&mut io::stdin().lock(), writer, &namer, raw, None),
+        Some(cli::Command::Decode { raw }) => decoder(&mut io::stdin().lock(), writer, &namer, raw),
+        Some(cli::Command::Stats { max_words, json }) => {
+            stats::write(writer, generator, max_words, cli.suffix, removed, json)
+                .map_err(suppress_disconnect)?;
+            writer.flush().map_err(suppress_disconnect)
+        }
+        _ => {
+            if let Some(ref key) = cli.from_key {
+                keyed_printer(writer, &namer, [Ok(key.clone())], None)
+            } else if cli.keys_from_stdin {
+                keyed_printer(writer, &namer, io::stdin().lock().lines(), None)
+            } else {
+                printer(cli, writer, &excluding(cli, namer)?, rng, count)
+            }
+        }
+    }
+}
+
+/// Print names derived from keys, or encode or decode bytes, as [`emit`] does,
+/// warning of each derived name that is blocked.
+fn emit_derived<OUT>(
+    cli: &Cli,
+    writer: &mut OUT,
+    petnames: &Petnames,
+    words: u8,
+    blocked: &BlockedNames,
+) -> Result<(), Error>
+where
+    OUT: io::Write,
+{
+    let namer = namer(cli, petnames, words);
+    match cli.command {
+        Some(cli::Command::Encode { raw }) => {
+            encoder(&mut io::stdin().lock(), writer, &namer, raw, Some(blocked))
+        }
+        Some(cli::Command::Decode { raw }) => decoder(&mut io::stdin().lock(), writer, &namer, raw),
+        _ => match cli.from_key {
+            Some(ref key) => keyed_printer(writer, &namer, [Ok(key.clone())], Some(blocked)),
+            None => keyed_printer(writer, &namer, io::stdin().lock().lines(), Some(blocked)),
+        },
+    }
+}
+
+/// Finds names derived from keys or bytes that the blocklist blocks. These
+/// are never skipped, since that would change every other name derived from
+/// a key, and every encoding.
+struct BlockedNames<'a, 'w> {
+    /// Names as derived, but without --case, so their words can be found.
+    namer: Namer<'a, Petnames<'w>>,
+    petnames: &'a Petnames<'w>,
+    blocklist: &'a Blocklist,
+    separator: &'a str,
+    /// The length of the suffix, if any, and the separator before it.
+    suffix: usize,
+}
+
+impl<'a, 'w> BlockedNames<'a, 'w> {
+    fn new(
+        cli: &'a Cli,
+        petnames: &'a Petnames<'w>,
+        blocklist: &'a Blocklist,
+        words: u8,
+        max_len: Option<usize>,
+    ) -> Self {
+        let mut namer = Namer::new(petnames, words, cli.separator());
+        if let Some(suffix) = cli.suffix {
+            namer = namer.with_suffix(suffix);
+        }
+        if let Some(max_len) = max_len {
+            namer = namer.with_max_len(max_len);
+        }
+        let suffix = cli.suffix.map_or(0, |suffix| cli.separator().len() + usize::from(suffix.length()));
+        Self { namer, petnames, blocklist, separator: cli.separator(), suffix }
+    }
+
+    /// Whether the name derived from `key` is blocked.
+    fn for_key(&self, key: &[u8]) -> bool {
+        self.namer.name_for_key(key).is_some_and(|name| self.blocks(&name))
+    }
+
+    /// Whether each of the names encoding `bytes` is blocked.
+    fn for_bytes(&self, bytes: &[u8]) -> Vec<bool> {
+        self.namer.encode(bytes).unwrap_or_default().iter().map(|name| self.blocks(name)).collect()
+    }
+
+    fn blocks(&self, name: &str) -> bool {
+        let name = &name[..name.len().saturating_sub(self.suffix)];
+        is_blocked(self.petnames, self.blocklist, name, self.separator)
+    }
+}
+
+/// Warn, on standard error, that `name` is blocked but was not skipped.
+fn warn_blocked(name: &str) {
+    eprintln!("warning: {name} is blocked, but names derived from keys or bytes are never skipped");
+}
+
+/// Whether `name`, parsed with `petnames`, has a word or pair of words that
+/// `blocklist` blocks.
+fn is_blocked(petnames: &Petnames, blocklist: &Blocklist, name: &str, separator: &str) -> bool {
+    petnames.parse(name, separator).is_ok_and(|parsed| {
+        let words: Vec<_> = parsed
+            .adverbs
+            .iter()
+            .chain(&parsed.adjective)
+            .chain([&parsed.noun])
+            .map(|word| word.word)
+            .collect();
+        blocklist.blocks(&words, separator)
+    })
+}
+
+/// A namer for the given generator, with the --case, --profile, and --suffix options.
+fn namer<'a, GEN: Generator>(cli: &'a Cli, generator: &'a GEN, words: u8) -> Namer<'a, GEN> {
+    let mut namer = Namer::new(generator, words, cli.separator());
+    if let Some(style) = cli.style() {
+        namer = namer.with_style(style);
+    }
+    if let Some(suffix) = cli.suffix {
+        namer = namer.with_suffix(suffix);
+    }
+    namer
+}
+
+/// The given namer, never generating the names in --exclude-file, if given.
+fn excluding<'a, GEN: Indexed>(cli: &Cli, namer: Namer<'a, GEN>) -> Result<Namer<'a, GEN>, Error> {
+    let Some(ref path) = cli.exclude_file else {
+        return Ok(namer);
+    };
+    let namer = namer.with_excluded(read_exclusions(path)?);
+    if namer.cardinality() == 0 {
+        return Err(Error::Cardinality("every name is excluded".to_string()));
+    }
+    Ok(namer)
+}
+
+fn keyed_printer<OUT, GEN, KEYS>(
+    writer: &mut OUT,
+    namer: &Namer<GEN>,
+    keys: KEYS,
+    blocked: Option<&BlockedNames>,
+) -> Result<(), Error>
+where
+    OUT: io::Write,
+    GEN: Indexed,
+    KEYS: IntoIterator<Item = io::Result<String>>,
+{
+    for key in keys {
+        let key = key?;
+        // Cardinality has already been checked, so there is always a name.
+        if let Some(name) = namer.name_for_key(key.as_bytes()) {
+            if blocked.is_some_and(|blocked| blocked.for_key(key.as_bytes())) {
+                warn_blocked(&name);
+            }
+            writeln!(writer, "{name}").map_err(suppress_disconnect)?;
+        }
+    }
+
+    writer.flush().map_err(suppress_disconnect)?;
+
+    Ok(())
 }
 
 fn printer<OUT, GEN, RNG>(
+    cli: &Cli,
     writer: &mut OUT,
     namer: &Namer<'_, GEN>,
     rng: &mut RNG,
@@ -213,11 +882,28 @@ where
     GEN: Generator,
     RNG: rand::Rng,
 {
+    // Names that must be different enough from each other are chosen all at
+    // once, before any are printed.
+    if cli.min_distance.is_some() || cli.different_first_words {
+        let mut batch = namer.distinct_batch(count.unwrap_or(cli.count), cli.min_distance.unwrap_or(0));
+        if cli.different_first_words {
+            batch = batch.different_first_words();
+        }
+        for name in batch.generate(rng).map_err(|err| Error::Batch(err.to_string()))? {
+            writeln!(writer, "{name}").map_err(suppress_disconnect)?;
+        }
+        return writer.flush().map_err(suppress_disconnect);
+    }
+
+    // Cardinality has already been checked, so running out of names means
+    // that something went wrong; say so rather than stopping silently.
+    let mut exhausted = false;
     let mut buf = String::new();
     match count {
         None => loop {
             namer.generate_into(&mut buf, rng);
             if buf.is_empty() {
+                exhausted = true;
                 break;
             } else {
                 writeln!(writer, "{buf}").map_err(suppress_disconnect)?;
@@ -228,6 +914,7 @@ where
             for _ in 0..n {
                 namer.generate_into(&mut buf, rng);
                 if buf.is_empty() {
+                    exhausted = true;
                     break;
                 } else {
                     writeln!(writer, "{buf}")?;
@@ -239,15 +926,104 @@ where
 
     writer.flush().map_err(suppress_disconnect)?;
 
+    if exhausted {
+        return Err(Error::Cardinality("no petnames left to choose from".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Encode bytes – hexadecimal unless `raw` – from `reader` as names.
+fn encoder<IN, OUT, GEN>(
+    reader: &mut IN,
+    writer: &mut OUT,
+    namer: &Namer<'_, GEN>,
+    raw: bool,
+    blocked: Option<&BlockedNames>,
+) -> Result<(), Error>
+where
+    IN: io::Read,
+    OUT: io::Write,
+    GEN: Indexed,
+{
+    let mut input = Vec::new();
+    reader.read_to_end(&mut input)?;
+    let bytes = if raw { input } else { parse_hex(&input)? };
+    let names = namer.encode(&bytes).map_err(|err| Error::Encoding(err.to_string()))?;
+    if let Some(blocked) = blocked {
+        names.iter().zip(blocked.for_bytes(&bytes)).filter(|(_, blocked)| *blocked).for_each(|(name, _)| {
+            warn_blocked(name);
+        });
+    }
+    for name in names {
+        writeln!(writer, "{name}").map_err(suppress_disconnect)?;
+    }
+
+    writer.flush().map_err(suppress_disconnect)?;
+
+    Ok(())
+}
+
+/// Decode names, one per line, from `reader` into bytes, written in
+/// hexadecimal unless `raw`.
+fn decoder<IN, OUT, GEN>(
+    reader: &mut IN,
+    writer: &mut OUT,
+    namer: &Namer<'_, GEN>,
+    raw: bool,
+) -> Result<(), Error>
+where
+    IN: io::Read,
+    OUT: io::Write,
+    GEN: Indexed,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let names: Vec<&str> = input.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let bytes = namer.decode(&names).map_err(|err| Error::Encoding(err.to_string()))?;
+    if raw {
+        writer.write_all(&bytes).map_err(suppress_disconnect)?;
+    } else {
+        let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        writeln!(writer, "{hex}").map_err(suppress_disconnect)?;
+    }
+
+    writer.flush().map_err(suppress_disconnect)?;
+
     Ok(())
 }
 
+/// Parse hexadecimal, ignoring whitespace, colons, and a leading `0x`.
+fn parse_hex(input: &[u8]) -> Result<Vec<u8>, Error> {
+    let invalid = || Error::Encoding("input is not hexadecimal".to_string());
+    let input = std::str::from_utf8(input).map_err(|_| invalid())?.trim();
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    let digits = input
+        .chars()
+        .filter(|&c| !c.is_whitespace() && c != ':')
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)?;
+    if !digits.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    Ok(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
 enum Words {
     Custom(String, String, String),
     Builtin,
 }
 
 impl Words {
+    // Load custom word lists if the command-line options ask for them.
+    fn for_cli(cli: &Cli) -> Result<Self, Error> {
+        match cli.directory {
+            Some(ref dirname) => Self::load(dirname),
+            None => Ok(Self::Builtin),
+        }
+    }
+
     // Load word lists from the given directory. This function expects to find three
     // files in that directory: `adjectives.txt`, `adverbs.txt`, and `nouns.txt`.
     // Each should be valid UTF-8, and contain words separated by whitespace.
@@ -269,6 +1045,17 @@ impl Words {
     }
 }
 
+/// Read names to exclude, one per line, from a file or, given `-`, from
+/// standard input.
+fn read_exclusions(path: &path::Path) -> Result<Vec<String>, Error> {
+    let names = if path == path::Path::new("-") {
+        io::read_to_string(io::stdin().lock())?
+    } else {
+        read_file_to_string(path)?
+    };
+    Ok(names.lines().map(str::trim).filter(|name| !name.is_empty()).map(String::from).collect())
+}
+
 fn read_file_to_string<P: AsRef<path::Path>>(path: P) -> Result<String, Error> {
     fs::read_to_string(&path).map_err(|error| Error::FileIo(path.as_ref().to_path_buf(), error))
 }
@@ -298,6 +1085,39 @@ mod integration {
         String::from_utf8(stdout).unwrap()
     }
 
+    /// Word lists in a temporary directory, for --dir.
+    struct WordLists(tempfile::TempDir);
+
+    impl WordLists {
+        fn new(adverbs: &str, adjectives: &str, nouns: &str) -> anyhow::Result<Self> {
+            let lists = Self(tempfile::TempDir::with_prefix("petname")?);
+            lists.write("adverbs", adverbs)?;
+            lists.write("adjectives", adjectives)?;
+            lists.write("nouns", nouns)?;
+            Ok(lists)
+        }
+
+        /// Replace the words of one list, e.g. "nouns".
+        fn write(&self, list: &str, words: &str) -> std::io::Result<()> {
+            fs::write(self.0.path().join(format!("{list}.txt")), words)
+        }
+
+        /// Parse the given options, with these word lists.
+        fn cli(&self, extra: &[&str]) -> super::Cli {
+            let args: [std::ffi::OsString; 3] = ["petname".into(), "--dir".into(), self.0.path().into()];
+            super::Cli::parse_from(args.into_iter().chain(extra.iter().map(Into::into)))
+        }
+
+        /// Run with these word lists and the given options, capturing the
+        /// output or the error message.
+        fn run(&self, extra: &[&str]) -> Result<String, String> {
+            let mut stdout = Vec::new();
+            super::run(self.cli(extra), &mut stdout)
+                .map(|()| String::from_utf8(stdout).unwrap())
+                .map_err(|err| err.to_string())
+        }
+    }
+
     #[test]
     fn option_words() {
         let cli = super::Cli::parse_from(["petname", "--words=5"]);
@@ -310,6 +1130,14 @@ mod integration {
         assert_eq!(run_and_capture(cli).split_whitespace().map(str::len).max(), Some(3))
     }
 
+    #[test]
+    fn option_min_letters() {
+        let cli = super::Cli::parse_from(["petname", "--min-letters=5", "--count=100", "--separator= "]);
+        assert_eq!(run_and_capture(cli).split_whitespace().map(str::len).min(), Some(5));
+        let cli = super::Cli::parse_from(["petname", "--min-letters=4", "--letters=4", "--count=100"]);
+        assert!(run_and_capture(cli).lines().all(|name| name.len() == "four-four".len()));
+    }
+
     #[test]
     fn option_separator() {
         let cli = super::Cli::parse_from(["petname", "--separator=<:>"]);
@@ -395,6 +1223,17 @@ mod integration {
         assert_eq!(first_letters, ['a'].into());
     }
 
+    #[test]
+    fn option_alliterate_with_sound() -> anyhow::Result<()> {
+        // By sound, c begins cute and kitten, but not cheerful.
+        let lists = WordLists::new("", "cheerful cute", "kitten")?;
+        let options = ["-A", "c", "--alliterate=sound"];
+        assert_eq!(lists.run(&[&options[..], &["--count=2"]].concat()), Ok("cute-kitten\n".repeat(2)));
+        assert_eq!(lists.run(&[&options[..], &["--max-length=11"]].concat()), Ok("cute-kitten\n".into()));
+        assert!(lists.run(&[&options[..], &["check", "cheerful-kitten"]].concat()).is_err());
+        Ok(())
+    }
+
     #[test]
     fn compat_ubuntu() {
         let cli = super::Cli::parse_from(["petname", "--ubuntu", "--words=3"]);
@@ -409,6 +1248,502 @@ mod integration {
         assert_eq!(run_and_capture(cli), "meaningfully-enthralled-vendace\n");
     }
 
+    #[test]
+    fn command_check() {
+        let cli = super::Cli::parse_from(["petname", "--lists=small", "--from-key=abc", "--words=3"]);
+        let name = run_and_capture(cli);
+        let cli = super::Cli::parse_from(["petname", "--lists=small", "check", name.trim()]);
+        assert_eq!(run_and_capture(cli), format!("{}: ok\n", name.trim()));
+    }
+
+    #[test]
+    fn command_check_rejects_invalid_names() {
+        let cli = super::Cli::parse_from(["petname", "check", "--words=2", "zzzz-yyyy", "able"]);
+        let mut stdout = Vec::new();
+        assert!(super::run(cli, &mut stdout).is_err());
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "zzzz-yyyy: unknown word: zzzz\nable: not a name that these word lists can produce\n",
+        );
+    }
+
+    #[test]
+    fn command_check_with_words_and_alliteration() -> anyhow::Result<()> {
+        let lists = WordLists::new("very", "brave calm", "bear cat")?;
+        let check = |extra: &[&str], name: &str| {
+            let args: Vec<&str> = extra.iter().copied().chain(["check", name]).collect();
+            lists.run(&args).is_ok()
+        };
+
+        assert!(check(&[], "brave-cat"));
+        assert!(!check(&["--alliterate"], "brave-cat"));
+        assert!(check(&["--alliterate"], "calm-cat"));
+        assert!(!check(&["--alliterate-with=b"], "calm-cat"));
+        assert!(check(&[], "very-brave-bear"));
+        Ok(())
+    }
+
+    #[test]
+    fn option_template() -> anyhow::Result<()> {
+        let lists = WordLists::new("very", "gold", "cat")?;
+        let names = lists.run(&["--template", "{adjective}.{noun}-{digits:2}"]).unwrap();
+        let name = names.trim_end();
+        assert!(name.starts_with("gold.cat-"), "{name:?}");
+        assert!(name[9..].len() == 2 && name[9..].bytes().all(|b| b.is_ascii_digit()), "{name:?}");
+        assert_eq!(lists.run(&["--template", "{{{adverb}}}"]).unwrap(), "{very}\n");
+
+        for template in ["{noun", "{pronoun}", "{adjective}{noun}"] {
+            let result = super::run(lists.cli(&["--template", template]), &mut Vec::new());
+            assert!(matches!(result, Err(super::Error::Template(_))), "{template}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn option_distinct_words() -> anyhow::Result<()> {
+        let lists = WordLists::new("very", "gold", "gold silver")?;
+        assert_eq!(lists.run(&["--distinct-words", "--count=10"]).unwrap(), "gold-silver\n".repeat(10));
+        let result = super::run(lists.cli(&["--distinct-words", "--words=4"]), &mut Vec::new());
+        assert!(matches!(result, Err(super::Error::Cardinality(_))));
+
+        let mut stdout = Vec::new();
+        let cli = lists.cli(&["--distinct-words", "check", "gold-gold", "gold-silver"]);
+        assert!(super::run(cli, &mut stdout).is_err());
+        assert_eq!(String::from_utf8(stdout)?, "gold-gold: repeats a word\ngold-silver: ok\n");
+        Ok(())
+    }
+
+    #[test]
+    fn option_distinct_words_is_quick_with_large_lists() {
+        let cli = super::Cli::parse_from([
+            "petname",
+            "--lists=large",
+            "--distinct-words",
+            "--words=4",
+            "--count=1",
+        ]);
+        let start = std::time::Instant::now();
+        let name = run_and_capture(cli);
+        // Counting names once took seconds for each name generated.
+        assert!(start.elapsed() < std::time::Duration::from_secs(2), "took {:?}", start.elapsed());
+        assert_eq!(name.lines().count(), 1);
+    }
+
+    #[test]
+    fn blocked_names_are_found_but_not_skipped() -> anyhow::Result<()> {
+        let petnames = petname::Petnames::new("brave calm", "", "bear pig");
+        let blocklist = petname::Blocklist::parse("calm pig")?;
+        let cli = super::Cli::parse_from(["petname", "--words=2", "--case=title", "--suffix=digits:2"]);
+        let blocked = super::BlockedNames::new(&cli, &petnames, &blocklist, 2, None);
+        let namer = super::namer(&cli, &petnames, 2);
+        let mut found = 0;
+        for key in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+            let name = namer.name_for_key(key.as_bytes()).unwrap();
+            assert_eq!(blocked.for_key(key.as_bytes()), name.starts_with("Calm-Pig-"), "{name}");
+            found += usize::from(blocked.for_key(key.as_bytes()));
+        }
+        assert!(found > 0);
+        let names = namer.encode(&[0xde, 0xad]).unwrap();
+        let found: Vec<bool> = names.iter().map(|name| name.starts_with("Calm-Pig-")).collect();
+        assert_eq!(blocked.for_bytes(&[0xde, 0xad]), found);
+        Ok(())
+    }
+
+    #[test]
+    fn option_exclude_file() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "brave calm", "bear cat")?;
+        let exclude = lists.0.path().join("exclude.txt");
+        let exclude = format!("--exclude-file={}", exclude.display());
+        lists.write("exclude", "brave-bear\n  calm-cat \n\ncalm-bear\n")?;
+        assert_eq!(lists.run(&[&exclude, "--count=10"]), Ok("brave-cat\n".repeat(10)));
+        // Names chosen all at once are never excluded either.
+        for option in ["--different-first-words", "--min-distance=0"].repeat(10) {
+            assert_eq!(lists.run(&[&exclude, option]), Ok("brave-cat\n".into()), "{option}");
+        }
+        // Names that fit are excluded as they are printed.
+        assert_eq!(lists.run(&[&exclude, "--count=10", "--max-length=9"]), Ok("brave-cat\n".repeat(10)));
+        assert_eq!(lists.run(&[&exclude, "--count=10", "--profile=k8s-name"]), Ok("brave-cat\n".repeat(10)));
+
+        lists.write("exclude", "brave-bear\ncalm-cat\ncalm-bear\nbrave-cat\n")?;
+        for option in ["--stream", "--count=3"] {
+            assert_eq!(
+                lists.run(&[&exclude, option]),
+                Err("cardinality is zero: every name is excluded".into())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn option_exclude_file_compares_names_as_printed() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "brave calm", "bear cat")?;
+        let exclude = lists.0.path().join("exclude.txt");
+        let exclude = format!("--exclude-file={}", exclude.display());
+        lists.write("exclude", "Brave-Bear\nCalm-Cat\nCalm-Bear\n")?;
+        assert_eq!(lists.run(&[&exclude, "--count=10", "--case=title"]), Ok("Brave-Cat\n".repeat(10)));
+        let names = lists.run(&[&exclude, "--count=100"]).unwrap();
+        assert!(names.lines().any(|name| name == "calm-cat"), "{names}");
+
+        lists.write("adjectives", "brave")?;
+        lists.write("nouns", "cat")?;
+        lists.write("exclude", &(0..9).map(|digit| format!("brave-cat-{digit}\n")).collect::<String>())?;
+        assert_eq!(lists.run(&[&exclude, "--count=10", "--suffix=digits:1"]), Ok("brave-cat-9\n".repeat(10)));
+        Ok(())
+    }
+
+    #[test]
+    fn option_exclude_file_counts_names_split_another_way() -> anyhow::Result<()> {
+        // "x-ray-fish" is both "x-ray" + "fish" and "x" + "ray-fish".
+        let lists = WordLists::new("", "x-ray x", "fish ray-fish")?;
+        let exclude = lists.0.path().join("exclude.txt");
+        let exclude = format!("--exclude-file={}", exclude.display());
+        lists.write("exclude", "x-ray-fish\nx-fish\n")?;
+        assert_eq!(lists.run(&[&exclude, "--count=3"]), Ok("x-ray-ray-fish\n".repeat(3)));
+        lists.write("exclude", "x-ray-fish\nx-fish\nx-ray-ray-fish\n")?;
+        for option in ["--stream", "--count=3"] {
+            assert_eq!(
+                lists.run(&[&exclude, option]),
+                Err("cardinality is zero: every name is excluded".into())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn command_encode_and_decode() {
+        let petnames = super::Petnames::small();
+        let namer = petnames.namer(2, "-");
+        let mut names = Vec::new();
+        super::encoder(&mut &b"0xDE:AD:be:ef\n"[..], &mut names, &namer, false, None).unwrap();
+        let mut hex = Vec::new();
+        super::decoder(&mut &names[..], &mut hex, &namer, false).unwrap();
+        assert_eq!(String::from_utf8(hex).unwrap(), "deadbeef\n");
+        let mut raw = Vec::new();
+        super::decoder(&mut &names[..], &mut raw, &namer, true).unwrap();
+        assert_eq!(raw, [0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn command_encode_and_decode_reject_case_and_profile() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "brave calm", "cat fox")?;
+        for command in ["encode", "decode"] {
+            for option in ["--case=title", "--profile=k8s-name"] {
+                assert_eq!(
+                    lists.run(&[option, command]),
+                    Err("unsupported: --case and --profile are not supported by encode or decode".into())
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn command_encode_rejects_bad_hex() {
+        let petnames = super::Petnames::small();
+        let namer = petnames.namer(2, "-");
+        for input in ["abc", "xy", "0x0g"] {
+            assert!(super::encoder(&mut input.as_bytes(), &mut Vec::new(), &namer, false, None).is_err());
+        }
+    }
+
+    #[test]
+    fn option_from_key() {
+        let cli = super::Cli::parse_from(["petname", "--from-key=4c6fd1a", "--words=3"]);
+        let name = run_and_capture(cli);
+        // This must never change: names derived from keys are stable.
+        assert_eq!(name, "witlessly-sharp-tuatara\n");
+        let cli = super::Cli::parse_from(["petname", "--from-key=4c6fd1b", "--words=3"]);
+        assert_ne!(run_and_capture(cli), name);
+    }
+
+    #[test]
+    fn command_stats() {
+        let cli = super::Cli::parse_from(["petname", "--letters=4", "stats", "--max-words=3"]);
+        let stats = run_and_capture(cli);
+        assert!(stats.starts_with("adverbs: "), "stats were {stats:?}");
+        assert_eq!(stats.lines().filter(|line| line.starts_with(['1', '2', '3'])).count(), 3);
+        let cli = super::Cli::parse_from(["petname", "--alliterate-with=b", "stats", "--json"]);
+        let stats = run_and_capture(cli);
+        assert!(stats.starts_with(r#"{"lists":"#), "stats were {stats:?}");
+        assert!(stats.ends_with("}]}\n"), "stats were {stats:?}");
+        assert_eq!(stats.matches(r#""letter":"b""#).count(), 1);
+    }
+
+    #[test]
+    fn option_case() {
+        let name = |args: &[&str]| {
+            let args = ["petname", "--from-key=4c6fd1a", "--words=3"].iter().chain(args);
+            run_and_capture(super::Cli::parse_from(args))
+        };
+        assert_eq!(name(&["--case=pascal"]), "WitlesslySharpTuatara\n");
+        assert_eq!(name(&["--case=screaming-snake"]), "WITLESSLY_SHARP_TUATARA\n");
+        // An explicit separator takes precedence.
+        assert_eq!(name(&["--case=title", "--separator= "]), "Witlessly Sharp Tuatara\n");
+        assert_eq!(name(&["--case=camel", "--separator=."]), "witlessly.Sharp.Tuatara\n");
+    }
+
+    #[test]
+    fn option_suffix() {
+        let name = |args: &[&str]| {
+            let args = ["petname", "--from-key=4c6fd1a", "--words=3"].iter().chain(args);
+            run_and_capture(super::Cli::parse_from(args))
+        };
+        for (option, length, alphabet) in [
+            ("--suffix=digits:4", 4, petname::Alphabet::Digits),
+            ("--suffix=alnum:5", 5, petname::Alphabet::Alphanumeric),
+            ("--suffix=consonants:3", 3, petname::Alphabet::Consonants),
+        ] {
+            let suffixed = name(&[option]);
+            let (words, suffix) = suffixed.trim_end().rsplit_once('-').unwrap();
+            assert_eq!(words.split('-').count(), 3, "{suffixed:?}");
+            assert!(suffix.len() == length && suffix.chars().all(|c| alphabet.chars().contains(c)));
+            // Names are stable for a key.
+            assert_eq!(name(&[option]), suffixed);
+        }
+        assert!(super::Cli::try_parse_from(["petname", "--suffix=digits"]).is_err());
+        assert!(super::Cli::try_parse_from(["petname", "--suffix=hex:4"]).is_err());
+    }
+
+    #[test]
+    fn option_prefer_common() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "fat:1 thin:0", "cat dog:0")?;
+        assert_eq!(lists.run(&["--prefer-common", "--count=10"]).unwrap(), "fat-cat\n".repeat(10));
+        // Without --prefer-common, weights are ignored but not printed.
+        assert!(!lists.run(&[]).unwrap().contains(':'));
+        let stats = lists.run(&["--prefer-common", "--count=10", "stats"]).unwrap();
+        assert!(stats.contains("effective entropy (bits)"), "{stats}");
+
+        // Built-in lists have weights of their own.
+        let cli = super::Cli::parse_from(["petname", "--prefer-common", "--lists=large", "--min-entropy=40"]);
+        assert!(!run_and_capture(cli).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn option_blocklist() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "lazy quick", "dog fox")?;
+        lists.write("blocklist", "quick # Not so quick.\nlazy fox\n")?;
+        let blocklist = lists.0.path().join("blocklist.txt");
+        let blocklist = blocklist.to_str().unwrap();
+
+        let names = lists.run(&["--blocklist", blocklist, "--no-default-blocklist", "--count=20"]);
+        assert_eq!(names.unwrap(), "lazy-dog\n".repeat(20));
+
+        // The built-in blocklist blocks "lazy dog" too.
+        let error = lists.run(&["--blocklist", blocklist, "--count=20"]).unwrap_err();
+        assert_eq!(error, "cardinality is zero: every name is blocked");
+        Ok(())
+    }
+
+    #[test]
+    fn option_no_default_blocklist() {
+        let check = |args: &[&str]| {
+            let args = ["petname"].iter().chain(args).chain(&["check", "big-pig"]);
+            let mut stdout = Vec::new();
+            let result = super::run(super::Cli::parse_from(args), &mut stdout);
+            (result.is_ok(), String::from_utf8(stdout).unwrap())
+        };
+        assert_eq!(check(&[]), (false, "big-pig: is blocked\n".to_string()));
+        assert_eq!(check(&["--no-default-blocklist"]), (true, "big-pig: ok\n".to_string()));
+    }
+
+    #[test]
+    fn option_max_length() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "big enormous", "ant hippopotamus")?;
+        assert_eq!(lists.run(&["--max-length=11", "--count=10"]).unwrap(), "big-ant\n".repeat(10));
+
+        // The separator and suffix count towards the length.
+        let output = lists.run(&["--max-length=14", "--suffix=digits:4", "--separator=__"]).unwrap();
+        assert!(output.starts_with("big__ant__"), "{output}");
+        assert_eq!(output.trim_end().len(), 14);
+
+        let error = lists.run(&["--max-length=6"]).unwrap_err();
+        assert_eq!(error, "cardinality is zero: no names fit in 6 characters");
+        Ok(())
+    }
+
+    #[test]
+    fn option_profile() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "Big o'clock", "path type")?;
+
+        assert_eq!(lists.run(&["--profile=rust-ident"]), Ok("big_path\n".into()));
+        assert_eq!(lists.run(&["--profile=env-var", "--words=1", "--count=3"]), Ok("TYPE\n".repeat(3)));
+        let output = lists.run(&["--profile=python-ident", "--suffix=digits:2"]).unwrap();
+        assert!(output.starts_with("big_"), "{output}");
+        assert_eq!(
+            lists.run(&["--profile=env-var", "--suffix=alnum:2"]),
+            Err("cannot keep to profile: a suffix with letters is not valid with the env-var profile".into())
+        );
+
+        // Every noun is reserved as a file name on Windows, or has an
+        // underscore, which DNS labels do not allow.
+        for (nouns, profile) in [("con nul", "filename"), ("snake_case", "dns-label")] {
+            lists.write("nouns", nouns)?;
+            let error = lists.run(&[&format!("--profile={profile}")]).unwrap_err();
+            assert!(error.ends_with(&format!("valid with the {profile} profile")), "{error}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn blocked_pairs_are_avoided_with_max_length_and_groups() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "big small", "pig wig")?;
+        lists.write("blocklist", "big pig")?;
+        let blocklist = format!("--blocklist={}", lists.0.path().join("blocklist.txt").display());
+        for options in
+            [&["--max-length=9"][..], &["--profile=dns-label"], &["--rhyme"], &["--rhyme", "--max-length=9"]]
+        {
+            let args: Vec<&str> =
+                [blocklist.as_str(), "--count=50"].into_iter().chain(options.iter().copied()).collect();
+            let output = lists.run(&args).unwrap();
+            assert!(output.lines().all(|name| name != "big-pig"), "{options:?}");
+        }
+        assert_eq!(lists.run(&[&blocklist, "--rhyme", "--count=2"]), Ok("big-wig\n".repeat(2)));
+        Ok(())
+    }
+
+    #[test]
+    fn option_rhyme() -> anyhow::Result<()> {
+        let lists = WordLists::new("chilly", "mellow silly yellow", "cat fellow lily")?;
+
+        assert_eq!(lists.run(&["--rhyme", "--words=3", "--count=3"]), Ok("chilly-silly-lily\n".repeat(3)));
+        let output = lists.run(&["--rhyme", "--count=20"]).unwrap();
+        for name in output.lines() {
+            assert!(["mellow-fellow", "yellow-fellow", "silly-lily"].contains(&name), "{name}");
+        }
+        assert_eq!(
+            lists.run(&["--rhyme", "check", "yellow-fellow", "silly-cat"]).unwrap_err(),
+            "invalid names: 1 of 2 names are not valid"
+        );
+
+        // No adjective rhymes with the only noun.
+        lists.write("nouns", "cat")?;
+        assert_eq!(
+            lists.run(&["--rhyme"]),
+            Err("cardinality is zero: word lists have no rhymes in common".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn option_alliterate_sound() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "cheerful knowing", "kitten nightingale")?;
+
+        assert_eq!(lists.run(&["--alliterate", "--count=3"]), Ok("knowing-kitten\n".repeat(3)));
+        assert_eq!(lists.run(&["-a", "--count=3"]), Ok("knowing-kitten\n".repeat(3)));
+        assert_eq!(lists.run(&["--alliterate=letter", "--count=3"]), Ok("knowing-kitten\n".repeat(3)));
+        assert_eq!(lists.run(&["--alliterate=sound", "--count=3"]), Ok("knowing-nightingale\n".repeat(3)));
+        assert_eq!(
+            lists.run(&["--alliterate=sound", "check", "knowing-nightingale", "knowing-kitten"]),
+            Err("invalid names: 1 of 2 names are not valid".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn option_group_by() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "brave cold", "bream crane")?;
+
+        assert_eq!(lists.run(&["--group-by=first2", "--count=3"]), Ok("brave-bream\n".repeat(3)));
+        assert_eq!(lists.run(&["--group-by=last", "--count=3"]), Ok("brave-crane\n".repeat(3)));
+        let output = lists.run(&["--group-by=length", "--count=20"]).unwrap();
+        for name in output.lines() {
+            assert!(["brave-bream", "brave-crane"].contains(&name), "{name}");
+        }
+        assert_eq!(
+            lists.run(&["--group-by=last", "check", "brave-crane", "cold-crane"]),
+            Err("invalid names: 1 of 2 names are not valid".into())
+        );
+        assert_eq!(
+            lists.run(&["--group-by=length", "--max-length=10"]),
+            Err("cardinality is zero: no names fit in 10 characters".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn option_alliterate_ignores_case_and_accents() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "Émigré", "eagle")?;
+
+        assert_eq!(lists.run(&["--alliterate", "--count=2"]), Ok("Émigré-eagle\n".repeat(2)));
+        for letter in ["e", "E", "é", "È"] {
+            assert_eq!(lists.run(&["--alliterate-with", letter]), Ok("Émigré-eagle\n".into()), "{letter}");
+        }
+        assert_eq!(lists.run(&["--alliterate-with=É", "--max-length=20"]), Ok("Émigré-eagle\n".into()));
+        assert_eq!(
+            lists.run(&["--alliterate-with=É", "check", "Émigré-eagle"]),
+            Ok("Émigré-eagle: ok\n".into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn option_sample_by_group() -> anyhow::Result<()> {
+        // How often each group is chosen is tested in the library.
+        let lists = WordLists::new("", "able bold", "ant bee")?;
+        let output = lists.run(&["--alliterate", "--sample-by-group", "--count=20"]).unwrap();
+        assert!(output.lines().all(|name| name == "able-ant" || name == "bold-bee"), "{output}");
+        Ok(())
+    }
+
+    #[test]
+    fn option_phonetic_safe() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "bare", "bear knight night")?;
+        assert_eq!(lists.run(&["--phonetic-safe", "--count=10"]).unwrap(), "bare-knight\n".repeat(10));
+        let stats = lists.run(&["--phonetic-safe", "stats", "--max-words=1"]).unwrap();
+        assert!(stats.contains("\nremoved as sounding alike: 2\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn option_min_distance() -> anyhow::Result<()> {
+        let lists = WordLists::new("", "brave calm", "box fox otter otters")?;
+
+        // At most one of brave-box and brave-fox, etc.
+        let output = lists.run(&["--count=4", "--min-distance=2"]).unwrap();
+        let mut names: Vec<&str> = output.lines().collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), 4);
+        assert_eq!(names.iter().filter(|name| name.ends_with("ox")).count(), 2);
+
+        let mut stdout = Vec::new();
+        let error = super::run(lists.cli(&["--count=5", "--min-distance=2"]), &mut stdout).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "names are too similar: found only 4 of 5 names different enough from each other"
+        );
+        assert!(stdout.is_empty());
+
+        let output = lists.run(&["--count=2", "--different-first-words"]).unwrap();
+        let mut firsts: Vec<&str> = output.lines().map(|name| name.split('-').next().unwrap()).collect();
+        firsts.sort_unstable();
+        assert_eq!(firsts, ["brave", "calm"]);
+        Ok(())
+    }
+
+    #[test]
+    fn option_min_entropy_counts_suffix() {
+        // Medium lists give roughly 10 bits per word; digits:4 gives 13 more.
+        let cli = super::Cli::parse_from(["petname", "--min-entropy=24", "--suffix=digits:4"]);
+        assert_eq!(run_and_capture(cli).trim_end().split('-').count(), 3);
+    }
+
+    #[test]
+    fn option_min_entropy() {
+        // The medium lists give roughly 10, 20, and 31 bits for 1, 2, and 3 words.
+        for (bits, words) in [("1", 1), ("12", 2), ("24", 3)] {
+            let cli = super::Cli::parse_from(["petname", "--min-entropy", bits]);
+            assert_eq!(run_and_capture(cli).trim_end().split('-').count(), words, "bits = {bits}");
+        }
+    }
+
+    #[test]
+    fn option_min_entropy_out_of_reach() {
+        let cli = super::Cli::parse_from(["petname", "--min-entropy=100000"]);
+        assert!(matches!(super::run(cli, &mut Vec::new()), Err(super::Error::Entropy(_))));
+    }
+
     #[cfg(feature = "lang-turkish")]
     #[test]
     fn option_language_turkish() {
@@ -0,0 +1,282 @@
+//! Reversible encoding of bytes as petnames.
+//!
+//! Every name that an [`Indexed`] generator can produce has an index, so a
+//! sequence of names can carry a sequence of numbers, and those numbers can
+//! carry arbitrary bytes. Each name carries a fixed number of bits: the largest
+//! `b` such that `2^b` is no greater than the cardinality.
+//!
+//! The bytes encoded are a _frame_:
+//!
+//! 1. The length of the data, as an unsigned [LEB128] integer.
+//! 2. The data itself.
+//! 3. A checksum: the most significant 4 bytes, big-endian, of the 128-bit
+//!    [FNV-1a] hash of parts 1 and 2.
+//!
+//! The frame is split into `b`-bit chunks, most significant bit first, with
+//! the final chunk padded with zero bits. Each chunk is the index of a name.
+//!
+//! This is not a mixed-radix encoding of the whole frame, so each name wastes
+//! the fraction of a bit by which the base-2 logarithm of the cardinality
+//! exceeds `b`. With the medium word lists and 3 words, say, each name could
+//! carry 30.94 bits but carries 30, so about 3% more names are needed. In
+//! exchange, each name is decoded on its own, without big-integer arithmetic.
+//!
+//! [LEB128]: https://en.wikipedia.org/wiki/LEB128
+//! [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{hash, Indexed, Namer};
+
+/// Number of checksum bytes at the end of a frame.
+const CHECKSUM_LEN: usize = 4;
+
+impl<G: Indexed + ?Sized> Namer<'_, G> {
+    /// Encode `bytes` as a sequence of petnames.
+    ///
+    /// [`decode`][`Self::decode`] reverses this, given a [`Namer`] with the
+    /// same word lists, number of words, and separator. A checksum is
+    /// embedded, so a mistyped or misheard name is very likely to be detected
+    /// when decoding.
+    ///
+    /// More words per name – or bigger word lists – means fewer names.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "default-words")] {
+    /// let petnames = petname::Petnames::default();
+    /// let namer = petnames.namer(3, "-");
+    /// let names = namer.encode(&[0xde, 0xad, 0xbe, 0xef]).unwrap();
+    /// assert_eq!(namer.decode(&names).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+    /// # }
+    /// ```
+    ///
+    pub fn encode(&self, bytes: &[u8]) -> Result<Vec<String>, MnemonicError> {
+        let bits = self.bits_per_name()?;
+        let frame = frame(bytes);
+        let mut names = Vec::new();
+        let mut chunk = Chunk::default();
+        for bit in frame.iter().flat_map(|byte| (0..8).rev().map(move |shift| byte >> shift & 1 == 1)) {
+            chunk.push(bit);
+            if chunk.len == bits {
+                names.push(self.name_at(chunk.take())?);
+            }
+        }
+        if chunk.len > 0 {
+            while chunk.len < bits {
+                chunk.push(false);
+            }
+            names.push(self.name_at(chunk.take())?);
+        }
+        Ok(names)
+    }
+
+    /// Decode bytes from a sequence of petnames made by
+    /// [`encode`][`Self::encode`].
+    ///
+    /// This [`Namer`] must have the same word lists, number of words, and
+    /// separator as the one used to encode.
+    pub fn decode<S: AsRef<str>>(&self, names: &[S]) -> Result<Vec<u8>, MnemonicError> {
+        let bits = self.bits_per_name()?;
+        let mut bytes = Vec::new();
+        let mut byte = Chunk::default();
+        for name in names {
+            let name = name.as_ref();
+            let index = self
+                .generator
+                .index_of(name, self.words, self.separator)
+                // The index must fit in the number of bits each name carries.
+                .filter(|&index| index >> bits == 0)
+                .ok_or_else(|| MnemonicError::UnknownName(name.into()))?;
+            for shift in (0..bits).rev() {
+                byte.push(index >> shift & 1 == 1);
+                if byte.len == 8 {
+                    bytes.push(byte.take() as u8);
+                }
+            }
+        }
+        let data = unframe(&bytes)?;
+        // Trailing names that decode to zero bits would otherwise pass as
+        // padding.
+        if (frame(&data).len() * 8).div_ceil(bits as usize) != names.len() {
+            return Err(MnemonicError::Malformed);
+        }
+        Ok(data)
+    }
+
+    /// The number of bits that each name carries.
+    fn bits_per_name(&self) -> Result<u32, MnemonicError> {
+        match self.generator.cardinality(self.words) {
+            0 | 1 => Err(MnemonicError::Cardinality),
+            cardinality => Ok(u128::BITS - 1 - cardinality.leading_zeros()),
+        }
+    }
+
+    fn name_at(&self, index: u128) -> Result<String, MnemonicError> {
+        self.generator.name_at(index, self.words, self.separator).ok_or(MnemonicError::Cardinality)
+    }
+}
+
+/// Bits accumulated, most significant first.
+#[derive(Default)]
+struct Chunk {
+    value: u128,
+    len: u32,
+}
+
+impl Chunk {
+    fn push(&mut self, bit: bool) {
+        self.value = self.value << 1 | u128::from(bit);
+        self.len += 1;
+    }
+
+    fn take(&mut self) -> u128 {
+        core::mem::take(self).value
+    }
+}
+
+/// Wrap `data` in a frame, as described in the [module docs][`self`].
+fn frame(data: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(data.len() + 10 + CHECKSUM_LEN);
+    let mut len = data.len();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            frame.push(byte);
+            break;
+        }
+        frame.push(byte | 0x80);
+    }
+    frame.extend_from_slice(data);
+    frame.extend_from_slice(&checksum(&frame));
+    frame
+}
+
+/// Extract the data from a frame, as described in the [module docs][`self`].
+///
+/// `bytes` may have trailing zero bytes, left over from padding.
+fn unframe(bytes: &[u8]) -> Result<Vec<u8>, MnemonicError> {
+    let mut len: usize = 0;
+    let mut prefix = 0;
+    loop {
+        let byte = *bytes.get(prefix).ok_or(MnemonicError::Malformed)?;
+        let bits = usize::from(byte & 0x7f);
+        len |= bits
+            .checked_shl(7 * prefix as u32)
+            .filter(|shifted| shifted >> (7 * prefix) == bits)
+            .ok_or(MnemonicError::Malformed)?;
+        prefix += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    let end = prefix.checked_add(len).ok_or(MnemonicError::Malformed)?;
+    let (framed, rest) = bytes.split_at_checked(end).ok_or(MnemonicError::Malformed)?;
+    let (sum, padding) = rest.split_at_checked(CHECKSUM_LEN).ok_or(MnemonicError::Malformed)?;
+    if padding.iter().any(|&byte| byte != 0) {
+        return Err(MnemonicError::Malformed);
+    }
+    if checksum(framed) != sum {
+        return Err(MnemonicError::Checksum);
+    }
+    Ok(framed[prefix..].to_vec())
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = hash::fnv1a_128(bytes).to_be_bytes();
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// The reason that [`Namer::encode`] or [`Namer::decode`] failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MnemonicError {
+    /// There are fewer than 2 names to choose from, so names cannot carry
+    /// any information.
+    Cardinality,
+    /// A name could not have been produced by [`Namer::encode`] with these
+    /// word lists and options.
+    UnknownName(String),
+    /// The names do not encode a complete frame; perhaps a name is missing,
+    /// or there is one too many.
+    Malformed,
+    /// The checksum does not match; perhaps a name is wrong, or names are in
+    /// the wrong order.
+    Checksum,
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cardinality => write!(f, "too few names to choose from"),
+            Self::UnknownName(name) => write!(f, "not an encoded name: {name}"),
+            Self::Malformed => write!(f, "names are incomplete or have extra names"),
+            Self::Checksum => write!(f, "checksum does not match"),
+        }
+    }
+}
+
+impl core::error::Error for MnemonicError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use super::{frame, unframe, MnemonicError};
+    use crate::Petnames;
+
+    fn petnames() -> Petnames<'static> {
+        // 2 adjectives * 3 nouns = 6 two-word names: 2 bits per name.
+        Petnames::new("able bold", "very quite", "ant bee cow")
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        for len in [0, 1, 127, 128, 300] {
+            let data: Vec<u8> = (0..len).map(|n| n as u8).collect();
+            assert_eq!(unframe(&frame(&data)), Ok(data));
+        }
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let petnames = petnames();
+        for words in 1..=4 {
+            let namer = petnames.namer(words, "-");
+            for data in [&b""[..], b"a", b"hello, world", &[0xff; 33]] {
+                let names = namer.encode(data).unwrap();
+                assert_eq!(namer.decode(&names).as_deref(), Ok(data), "words = {words}");
+            }
+        }
+    }
+
+    #[test]
+    fn decode_detects_errors() {
+        let petnames = petnames();
+        let namer = petnames.namer(2, "-");
+        let mut names = namer.encode(b"key").unwrap();
+        assert_eq!(namer.decode(&names[..names.len() - 1]), Err(MnemonicError::Malformed));
+        let padded: Vec<String> =
+            names.iter().cloned().chain([namer.encode(b"").unwrap()[0].clone()]).collect();
+        assert_eq!(namer.decode(&padded), Err(MnemonicError::Malformed));
+        assert_eq!(namer.decode(&["able-dog"]), Err(MnemonicError::UnknownName("able-dog".into())));
+        // Wrong number of words.
+        assert_eq!(namer.decode(&["very-able-ant"]), Err(MnemonicError::UnknownName("very-able-ant".into())));
+        // Swap two different names, after those carrying the length prefix.
+        let (first, second) = (4..names.len())
+            .flat_map(|i| (i + 1..names.len()).map(move |j| (i, j)))
+            .find(|&(i, j)| names[i] != names[j])
+            .unwrap();
+        names.swap(first, second);
+        assert_eq!(namer.decode(&names), Err(MnemonicError::Checksum));
+    }
+
+    #[test]
+    fn encode_requires_a_choice_of_names() {
+        let petnames = Petnames::new("able", "", "ant");
+        assert_eq!(petnames.namer(2, "-").encode(b"x"), Err(MnemonicError::Cardinality));
+        assert_eq!(petnames.namer(2, "-").decode::<String>(&[]), Err(MnemonicError::Cardinality));
+    }
+}
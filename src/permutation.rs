@@ -0,0 +1,99 @@
+//! Constant-memory pseudo-random permutations of `0..n`.
+//!
+//! A balanced Feistel network is a bijection on any power-of-two domain,
+//! whatever its round function. To permute `0..n` for arbitrary `n` we use the
+//! smallest such domain (with an even number of bits) that covers `n`, and
+//! _cycle-walk_: re-encrypt any output that lands outside `0..n` until it lands
+//! inside. The domain is less than `4n`, so this takes few steps on average.
+
+/// Number of Feistel rounds. Four is enough for a good-looking shuffle; we do
+/// not need cryptographic strength.
+const ROUNDS: usize = 4;
+
+/// A seeded pseudo-random permutation of `0..n`.
+#[derive(Clone, Debug)]
+pub(crate) struct Permutation {
+    n: u128,
+    half_bits: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl Permutation {
+    /// A new permutation of `0..n` with round keys drawn from `rng`.
+    pub(crate) fn new(n: u128, rng: &mut dyn rand::Rng) -> Self {
+        // Bits needed to represent `n - 1`, rounded up to an even number, and
+        // at least 2 so that each half has at least one bit.
+        let bits = match n {
+            0 | 1 => 0,
+            n => u128::BITS - (n - 1).leading_zeros(),
+        };
+        let half_bits = bits.div_ceil(2).max(1);
+        let mut keys = [0u64; ROUNDS];
+        keys.iter_mut().for_each(|key| *key = rng.next_u64());
+        Self { n, half_bits, keys }
+    }
+
+    /// The image of `index`, which must be less than `n`.
+    pub(crate) fn apply(&self, index: u128) -> u128 {
+        debug_assert!(index < self.n);
+        let mut value = self.encrypt(index);
+        while value >= self.n {
+            value = self.encrypt(value);
+        }
+        value
+    }
+
+    fn encrypt(&self, value: u128) -> u128 {
+        let mask = u64::MAX >> (u64::BITS - self.half_bits);
+        let mut left = (value >> self.half_bits) as u64 & mask;
+        let mut right = value as u64 & mask;
+        for key in self.keys {
+            (left, right) = (right, left ^ (mix(right ^ key) & mask));
+        }
+        (u128::from(left) << self.half_bits) | u128::from(right)
+    }
+}
+
+/// The SplitMix64 finaliser; a cheap and well-distributed round function.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand::SeedableRng;
+
+    use super::Permutation;
+
+    fn permute(n: u128, seed: u64) -> Vec<u128> {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
+        let permutation = Permutation::new(n, &mut rng);
+        (0..n).map(|index| permutation.apply(index)).collect()
+    }
+
+    #[test]
+    fn permutation_is_a_bijection() {
+        for n in [1, 2, 3, 4, 5, 17, 100, 1000, 4096, 4097] {
+            let mut values = permute(n, n as u64);
+            values.sort_unstable();
+            assert_eq!(values, (0..n).collect::<Vec<_>>(), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn permutation_depends_on_seed() {
+        assert_ne!(permute(1000, 1), permute(1000, 2));
+        assert_eq!(permute(1000, 3), permute(1000, 3));
+    }
+
+    #[test]
+    fn permutation_handles_the_largest_domain() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let permutation = Permutation::new(u128::MAX, &mut rng);
+        assert!(permutation.apply(u128::MAX - 1) < u128::MAX);
+    }
+}
@@ -0,0 +1,325 @@
+//! Phonetic keys for English words, for finding words that sound alike, and
+//! the sounds that words begin with, for alliterating by sound.
+//!
+//! The key is a simplified [Metaphone]: silent letters are dropped, and
+//! letters that sound alike – like the `c` in `cat` and the `k` in `kit` –
+//! become the same code. Unlike Metaphone, vowels are kept, though only
+//! roughly: a run of vowels, like the `ea` in `bear`, counts as its first, and
+//! `a` and `e` count as the same. Words with the same key, like `knight` and
+//! `night`, or `bear` and `bare`, are easily confused when read aloud, whereas
+//! `cat`, `cut`, and `kit` are not.
+//!
+//! [Metaphone]: https://en.wikipedia.org/wiki/Metaphone
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+use crate::{Petnames, Weighted};
+
+/// The phonetic key of an English `word`: words with the same key sound alike.
+///
+/// Letters other than A to Z, in either case, are kept as they are, in lower
+/// case, and anything that is not a letter is ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(petname::phonetic_key("knight"), petname::phonetic_key("night"));
+/// assert_eq!(petname::phonetic_key("Bear"), petname::phonetic_key("bare"));
+/// assert_ne!(petname::phonetic_key("bear"), petname::phonetic_key("pear"));
+/// assert_ne!(petname::phonetic_key("cat"), petname::phonetic_key("cut"));
+/// ```
+///
+pub fn phonetic_key(word: &str) -> String {
+    let mut letters: Vec<char> =
+        word.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect();
+    // Some pairs of letters at the start of a word sound as only one.
+    match letters.as_slice() {
+        ['k' | 'g' | 'p', 'n', ..] | ['a', 'e', ..] | ['w', 'r', ..] => {
+            letters.remove(0);
+        }
+        ['w', 'h', ..] => {
+            letters.remove(1);
+        }
+        ['x', ..] => letters[0] = 's',
+        _ => {}
+    }
+
+    let at = |position: usize| letters.get(position).copied().unwrap_or_default();
+    let mut key = String::new();
+    for (position, &letter) in letters.iter().enumerate() {
+        let (before, after, later) =
+            (position.checked_sub(1).map_or('\0', at), at(position + 1), at(position + 2));
+        // Doubled letters sound as one, except in e.g. "accent".
+        if letter == before && letter != 'c' {
+            continue;
+        }
+        match letter {
+            // A silent e, as in "bare".
+            'e' if after == '\0' && position > 0 && !is_vowel(before) => {}
+            _ if is_vowel(letter) && is_vowel(before) => {}
+            'a' | 'e' => key.push('A'),
+            'i' | 'o' | 'u' => key.push(letter.to_ascii_uppercase()),
+            'b' if before == 'm' && after == '\0' => {}
+            'c' if (after == 'i' && later == 'a') || (after == 'h' && before != 's') => key.push('X'),
+            'c' if matches!(after, 'i' | 'e' | 'y') => key.push('S'),
+            'c' => key.push('K'),
+            'd' if after == 'g' && matches!(later, 'e' | 'i' | 'y') => key.push('J'),
+            'd' => key.push('T'),
+            'g' if after == 'h' && !is_vowel(later) && later != '\0' => {}
+            'g' if after == 'n' && (later == '\0' || letters[position + 2..] == ['e', 'd']) => {}
+            'g' if before == 'd' && matches!(after, 'e' | 'i' | 'y') => {}
+            'g' if matches!(after, 'e' | 'i' | 'y') => key.push('J'),
+            'g' => key.push('K'),
+            'h' if matches!(before, 'c' | 'g' | 'p' | 's' | 't') => {}
+            'h' if is_vowel(after) => key.push('H'),
+            'h' => {}
+            'k' if before == 'c' => {}
+            'k' | 'q' => key.push('K'),
+            'p' if after == 'h' => key.push('F'),
+            's' if after == 'h' || (after == 'i' && matches!(later, 'a' | 'o')) => key.push('X'),
+            't' if after == 'i' && matches!(later, 'a' | 'o') => key.push('X'),
+            't' if after == 'h' => key.push('0'),
+            't' if after == 'c' && later == 'h' => {}
+            'v' => key.push('F'),
+            'w' | 'y' if is_vowel(after) => key.push(letter.to_ascii_uppercase()),
+            'w' | 'y' => {}
+            'x' => key.push_str("KS"),
+            'z' => key.push('S'),
+            letter if letter.is_ascii_lowercase() => key.push(letter.to_ascii_uppercase()),
+            letter => key.push(letter),
+        }
+    }
+    key
+}
+
+fn is_vowel(letter: char) -> bool {
+    matches!(letter, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// The sound that an English `word` begins with, for alliterating by sound;
+/// `None` if the word has no letters.
+///
+/// Unlike [`phonetic_key`], this keeps apart sounds that differ only in
+/// whether they are voiced, like `g` and `k`, since `gold` and `cold` do not
+/// alliterate. Most sounds are the upper-case letter that usually spells them:
+/// `K` for the hard `c` in `cat`, `k`, and `q`; `S` for the soft `c` in
+/// `cell`, and for `psalm`; `N` for `knowing`, `gnu`, and `pneumatic`; `F` for
+/// `phoenix`; `R` for `wren`; `J` for the soft `g` in `gem`, as Metaphone does;
+/// and `Z` for `xylophone`. `C` is the `ch` in `cheerful`, `X` is the `sh` in
+/// `shy`, and `0` is the `th` in `thin`. Every vowel sound is `A`, since any
+/// vowel alliterates with any other. Letters other than A to Z are kept as
+/// they are, in lower case.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(petname::initial_sound("cat"), petname::initial_sound("kitten"));
+/// assert_eq!(petname::initial_sound("knowing"), petname::initial_sound("nightingale"));
+/// assert_ne!(petname::initial_sound("cheerful"), petname::initial_sound("cat"));
+/// assert_ne!(petname::initial_sound("gold"), petname::initial_sound("cold"));
+/// ```
+///
+pub fn initial_sound(word: &str) -> Option<char> {
+    let letters: Vec<char> =
+        word.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).take(3).collect();
+    Some(match *letters.as_slice() {
+        [] => return None,
+        ['k' | 'g' | 'p' | 'm', 'n', ..] => 'N',
+        ['w', 'r', ..] => 'R',
+        ['p', 's', ..] | ['s', 'c', 'e' | 'i' | 'y', ..] | ['c', 'e' | 'i' | 'y', ..] => 'S',
+        ['p', 't', ..] => 'T',
+        ['p', 'h', ..] => 'F',
+        ['t', 'h', ..] => '0',
+        ['s', 'h', ..] => 'X',
+        // As in "chrome" and "chlorine".
+        ['c', 'h', 'r' | 'l', ..] => 'K',
+        ['c', 'h', ..] => 'C',
+        ['c' | 'q', ..] => 'K',
+        ['g', 'e' | 'i' | 'y', ..] => 'J',
+        ['x', ..] => 'Z',
+        // As in "ytterbium", but not "yak".
+        ['y', next, ..] if !is_vowel(next) => 'A',
+        [letter, ..] if is_vowel(letter) => 'A',
+        [letter, ..] if letter.is_ascii_lowercase() => letter.to_ascii_uppercase(),
+        [letter, ..] => letter,
+    })
+}
+
+/// Whether to keep each adjective, adverb, and noun, in that order, when
+/// removing words that sound like another.
+///
+/// Within each list, the first word with each key is kept. Adverbs and nouns
+/// come next to adjectives in names, so are compared with them too, and of a
+/// word in each that sound alike, the one in the larger list is removed: a
+/// long list of adjectives, say, does not then starve a short list of nouns.
+/// Adverbs and nouns are never next to each other, so are not compared.
+fn phonetically_distinct(petnames: &Petnames) -> Vec<bool> {
+    let lists = [&petnames.adjectives, &petnames.adverbs, &petnames.nouns];
+    let keys = lists.map(|words| words.iter().map(|word| phonetic_key(word)).collect::<Vec<_>>());
+    let mut keep = keys.each_ref().map(|keys| {
+        let mut seen = BTreeSet::new();
+        keys.iter().map(|key| seen.insert(key)).collect::<Vec<_>>()
+    });
+    for other in [1, 2] {
+        let (larger, smaller) = if lists[0].len() > lists[other].len() { (0, other) } else { (other, 0) };
+        let kept: BTreeSet<&String> =
+            keys[smaller].iter().zip(&keep[smaller]).filter(|(_, keep)| **keep).map(|(key, _)| key).collect();
+        for (key, keep) in keys[larger].iter().zip(&mut keep[larger]) {
+            *keep &= !kept.contains(key);
+        }
+    }
+    keep.concat()
+}
+
+impl Petnames<'_> {
+    /// Remove words that sound like another word, so that names are easy to
+    /// tell apart when read aloud. Returns the number of words removed.
+    ///
+    /// Of the words in a list that share a [`phonetic_key`], the first is
+    /// kept. Adjectives are also compared with adverbs and with nouns, which
+    /// come next to them in names, and of two words that sound alike, the one
+    /// in the larger list is removed. Adverbs and nouns are not compared with
+    /// each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use petname::Petnames;
+    /// let mut petnames = Petnames::new("bare brave", "", "bear cat cut knight night");
+    /// assert_eq!(petnames.dedupe_phonetically(), 2);
+    /// assert_eq!(petnames, Petnames::new("bare brave", "", "cat cut knight"));
+    /// ```
+    ///
+    pub fn dedupe_phonetically(&mut self) -> usize {
+        let size =
+            |petnames: &Self| petnames.adjectives.len() + petnames.adverbs.len() + petnames.nouns.len();
+        let before = size(self);
+        // Words are retained list by list, in the same order.
+        let mut keep = phonetically_distinct(self).into_iter();
+        self.retain(|_| keep.next().unwrap_or(true));
+        before - size(self)
+    }
+}
+
+impl Weighted<'_> {
+    /// Remove words that sound like another word, as
+    /// [`Petnames::dedupe_phonetically`] does, along with their weights.
+    /// Returns the number of words removed.
+    pub fn dedupe_phonetically(&mut self) -> usize {
+        let size =
+            |petnames: &Petnames| petnames.adjectives.len() + petnames.adverbs.len() + petnames.nouns.len();
+        let before = size(self.petnames());
+        let mut keep = phonetically_distinct(self.petnames()).into_iter();
+        self.retain(|_| keep.next().unwrap_or(true));
+        before - size(self.petnames())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{initial_sound, phonetic_key};
+    use crate::{Alliterations, Petnames};
+
+    #[test]
+    fn phonetic_key_matches_homophones() {
+        for (a, b) in [
+            ("knight", "night"),
+            ("bear", "bare"),
+            ("write", "right"),
+            ("whale", "wail"),
+            ("phase", "faze"),
+            ("sealing", "ceiling"),
+            ("plumb", "plum"),
+            ("gnome", "nome"),
+            ("xenon", "zenon"),
+        ] {
+            assert_eq!(phonetic_key(a), phonetic_key(b), "{a} and {b}");
+        }
+    }
+
+    #[test]
+    fn phonetic_key_distinguishes_other_words() {
+        for (a, b) in [
+            ("bear", "pear"),
+            ("fox", "box"),
+            ("thin", "tin"),
+            ("gem", "gum"),
+            ("ship", "sip"),
+            ("apt", "pat"),
+            ("cat", "cut"),
+            ("cut", "kit"),
+            ("cot", "cat"),
+        ] {
+            assert_ne!(phonetic_key(a), phonetic_key(b), "{a} and {b}");
+        }
+    }
+
+    #[test]
+    fn phonetic_key_codes() {
+        assert_eq!(phonetic_key("knight"), "NIT");
+        assert_eq!(phonetic_key("Thatch"), "0AX");
+        assert_eq!(phonetic_key("badge"), "BAJ");
+        assert_eq!(phonetic_key("quick"), "KUK");
+        assert_eq!(phonetic_key("yak"), "YAK");
+        assert_eq!(phonetic_key("o'clock"), "OKLOK");
+        assert_eq!(phonetic_key("ünïque"), "üNïKU");
+        assert_eq!(phonetic_key(""), "");
+    }
+
+    #[test]
+    fn dedupe_phonetically_compares_only_adjacent_lists() {
+        // Bare and bear sound alike; nouns are the larger list, so lose bear.
+        let mut petnames = Petnames::new("bare", "", "bear fox");
+        assert_eq!(petnames.dedupe_phonetically(), 1);
+        assert_eq!(petnames, Petnames::new("bare", "", "fox"));
+        // Adjectives are the larger list, so lose bare.
+        let mut petnames = Petnames::new("bare calm", "", "bear");
+        assert_eq!(petnames.dedupe_phonetically(), 1);
+        assert_eq!(petnames, Petnames::new("calm", "", "bear"));
+        // Adverbs and nouns never come next to each other.
+        let mut petnames = Petnames::new("calm", "well", "well");
+        assert_eq!(petnames.dedupe_phonetically(), 0);
+    }
+
+    #[test]
+    fn initial_sound_codes() {
+        for (words, sound) in [
+            (["cat", "kitten", "quail", "chrome"], 'K'),
+            (["cell", "sun", "psalm", "scene"], 'S'),
+            (["knowing", "gnu", "pneumatic", "mnemonic"], 'N'),
+            (["phoenix", "fox", "Fish", "f"], 'F'),
+            (["wren", "rat", "rhino", "Rook"], 'R'),
+            (["gem", "giraffe", "gym", "jay"], 'J'),
+            (["able", "eel", "otter", "ytterbium"], 'A'),
+            (["xenon", "zebra", "zoo", "xylophone"], 'Z'),
+            (["cheerful", "chick", "chalk", "cheap"], 'C'),
+            (["shy", "shark", "shrew", "sheep"], 'X'),
+            (["thin", "thrush", "the", "Theta"], '0'),
+            (["gold", "goat", "gull", "ghastly"], 'G'),
+            (["yak", "yellow", "yew", "yodel"], 'Y'),
+            (["wasp", "whale", "wolf", "weasel"], 'W'),
+        ] {
+            for word in words {
+                assert_eq!(initial_sound(word), Some(sound), "{word}");
+            }
+        }
+        assert_eq!(initial_sound("écu"), Some('é'));
+        assert_eq!(initial_sound("'"), None);
+    }
+
+    #[test]
+    fn alliterations_phonetic_groups_by_initial_sound() {
+        let petnames = Petnames::new("cheerful kind knowing phony", "", "cat cell chick fox gnu shark");
+        let alliterations = Alliterations::phonetic(petnames);
+        let expected: Alliterations = [
+            ('C', Petnames::new("cheerful", "", "chick")),
+            ('F', Petnames::new("phony", "", "fox")),
+            ('K', Petnames::new("kind", "", "cat")),
+            ('N', Petnames::new("knowing", "", "gnu")),
+            ('S', Petnames::new("", "", "cell")),
+            ('X', Petnames::new("", "", "shark")),
+        ]
+        .into();
+        assert_eq!(alliterations, expected);
+    }
+}
@@ -0,0 +1,346 @@
+//! Profiles of the places that names are used, like DNS labels and Rust
+//! identifiers, and a generator that keeps to one.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, ops::Range, str::FromStr};
+
+use crate::{Alphabet, ByLetters, Generator, Indexed, Lengths, Namer, Petnames, Style, Suffix, WordFinder};
+
+/// Names that Windows reserves for devices, in any case and with any
+/// extension.
+const WINDOWS_RESERVED: &[&str] = &[
+    "aux", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "con", "lpt1", "lpt2",
+    "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9", "nul", "prn",
+];
+
+/// Rust's strict and reserved keywords, as of the 2024 edition.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static",
+    "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Python's keywords, leaving out those with upper-case letters, which a
+/// lower-case name can never be.
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else",
+    "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not",
+    "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Environment variables that shells and common tools already use.
+const ENV_RESERVED: &[&str] = &[
+    "DISPLAY", "EDITOR", "HOME", "HOSTNAME", "IFS", "LANG", "LOGNAME", "MAIL", "OLDPWD", "PAGER", "PATH",
+    "PS1", "PWD", "SHELL", "TERM", "TMPDIR", "TZ", "USER",
+];
+
+/// Where names are to be used, and so which characters, case, separator,
+/// length, and words they may have.
+///
+/// Words with other characters, and words that are reserved on their own –
+/// like Windows' `CON`, or Rust's `type` – are left out of names entirely, so
+/// that no name is invalid, not even a name of one word.
+///
+/// # Examples
+///
+/// ```rust
+/// use petname::Profile;
+/// let profile: Profile = "rust-ident".parse().unwrap();
+/// assert_eq!(profile.separator(), "_");
+/// assert!(profile.allows_word("kind"));
+/// assert!(!profile.allows_word("type"));
+/// assert!(!profile.allows_word("o'clock"));
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    /// `dns-label`: a DNS label, as in a hostname, of at most 63 letters,
+    /// digits, and hyphens, beginning with a letter, in lower case.
+    DnsLabel,
+    /// `k8s-name`: the name of a Kubernetes object, an [RFC 1123] label: like
+    /// a DNS label, but it may begin with a digit.
+    ///
+    /// [RFC 1123]: https://kubernetes.io/docs/concepts/overview/working-with-objects/names/#dns-label-names
+    K8sName,
+    /// `filename`: a portable file name, of at most 255 letters, digits,
+    /// periods, underscores, and hyphens, in lower case, and not reserved by
+    /// Windows.
+    Filename,
+    /// `rust-ident`: a Rust identifier in `snake_case`, and not a keyword.
+    RustIdent,
+    /// `python-ident`: a Python identifier in `snake_case`, and not a keyword.
+    PythonIdent,
+    /// `env-var`: an environment variable in `SCREAMING_SNAKE_CASE`, and not
+    /// one that shells and common tools already use.
+    EnvVar,
+}
+
+impl Profile {
+    /// Every profile, in order.
+    pub const ALL: [Self; 6] =
+        [Self::DnsLabel, Self::K8sName, Self::Filename, Self::RustIdent, Self::PythonIdent, Self::EnvVar];
+
+    /// The name of this profile, e.g. `dns-label`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DnsLabel => "dns-label",
+            Self::K8sName => "k8s-name",
+            Self::Filename => "filename",
+            Self::RustIdent => "rust-ident",
+            Self::PythonIdent => "python-ident",
+            Self::EnvVar => "env-var",
+        }
+    }
+
+    /// The separator between words.
+    pub fn separator(self) -> &'static str {
+        self.style().separator()
+    }
+
+    /// The case of each word.
+    pub fn style(self) -> Style {
+        match self {
+            Self::DnsLabel | Self::K8sName | Self::Filename => Style::Kebab,
+            Self::RustIdent | Self::PythonIdent => Style::Snake,
+            Self::EnvVar => Style::ScreamingSnake,
+        }
+    }
+
+    /// The most characters in a name, if there is a limit.
+    pub fn max_len(self) -> Option<usize> {
+        match self {
+            Self::DnsLabel | Self::K8sName => Some(63),
+            Self::Filename => Some(255),
+            Self::RustIdent | Self::PythonIdent | Self::EnvVar => None,
+        }
+    }
+
+    /// Whether `word` may be used in a name, once in this profile's case.
+    pub fn allows_word(self, word: &str) -> bool {
+        let first = match word.chars().next() {
+            Some(first) => first,
+            None => return false,
+        };
+        match self {
+            Self::DnsLabel | Self::K8sName => {
+                (first.is_ascii_alphabetic() || (self == Self::K8sName && first.is_ascii_digit()))
+                    && word.ends_with(|c: char| c.is_ascii_alphanumeric())
+                    && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }
+            Self::Filename => {
+                first.is_ascii_alphanumeric()
+                    && word.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+                    && !reserved(WINDOWS_RESERVED, word.split('.').next().unwrap_or_default())
+            }
+            Self::RustIdent | Self::PythonIdent | Self::EnvVar => {
+                let keywords = match self {
+                    Self::RustIdent => RUST_KEYWORDS,
+                    Self::PythonIdent => PYTHON_KEYWORDS,
+                    _ => ENV_RESERVED,
+                };
+                !first.is_ascii_digit()
+                    && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !reserved(keywords, word)
+            }
+        }
+    }
+
+    /// Whether names may have `suffix`, once in this profile's case. Suffixes
+    /// are not restyled, so environment variables may have only digits.
+    pub fn allows_suffix(self, suffix: Suffix) -> bool {
+        self != Self::EnvVar || suffix.alphabet() == Alphabet::Digits || suffix.length() == 0
+    }
+}
+
+/// Whether `word` is one of `reserved`, ignoring case.
+fn reserved(reserved: &[&str], word: &str) -> bool {
+    reserved.iter().any(|reserved| reserved.eq_ignore_ascii_case(word))
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ProfileError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.name() == name)
+            .ok_or_else(|| ProfileError::Unknown(name.into()))
+    }
+}
+
+/// A [`Generator`] that wraps [`Petnames`] and generates only names that keep
+/// to a [`Profile`].
+///
+/// Words that the profile does not allow are left out, so names are chosen
+/// uniformly from those that remain. Use [`namer`][`Self::namer`] for a
+/// [`Namer`] with the profile's separator, case, and maximum length.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "default-rng")] {
+/// use petname::{Profile, Profiled};
+/// let petnames = petname::Petnames::new("Big o'clock", "", "type fn_ptr");
+/// let profiled = Profiled::new(&petnames, Profile::RustIdent).unwrap();
+/// let name = profiled.namer(2).iter(&mut rand::rng()).next().unwrap();
+/// assert_eq!(name, "big_fn_ptr");
+/// let keywords = petname::Petnames::new("", "", "self type");
+/// assert!(Profiled::new(&keywords, Profile::RustIdent).is_err());
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profiled<'a> {
+    petnames: Petnames<'a>,
+    profile: Profile,
+}
+
+impl<'a> Profiled<'a> {
+    /// Keep the words of `petnames` that `profile` allows.
+    ///
+    /// Fails if no nouns are left, since then there would be no names at all.
+    pub fn new(petnames: &Petnames<'a>, profile: Profile) -> Result<Self, ProfileError> {
+        let mut petnames = petnames.clone();
+        petnames.retain(|word| profile.allows_word(word));
+        if petnames.nouns.is_empty() {
+            Err(ProfileError::NoWords(profile))
+        } else {
+            Ok(Self { petnames, profile })
+        }
+    }
+
+    /// The profile that names keep to.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// The words that the profile allows.
+    pub fn petnames(&self) -> &Petnames<'a> {
+        &self.petnames
+    }
+
+    /// Create a [`Namer`] for names of `words` words with the profile's
+    /// separator, case, and maximum length, if any.
+    ///
+    /// A suffix added with [`Namer::with_suffix`] counts towards the maximum
+    /// length, but check [`Profile::allows_suffix`] first.
+    pub fn namer(&self, words: u8) -> Namer<'_, Self> {
+        let namer = Namer::new(self, words, self.profile.separator()).with_style(self.profile.style());
+        match self.profile.max_len() {
+            Some(max_len) => namer.with_max_len(max_len),
+            None => namer,
+        }
+    }
+}
+
+impl Generator for Profiled<'_> {
+    fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
+        self.petnames.generate_into(buf, rng, words, separator);
+    }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.petnames.word_ranges(name, words, separator)
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        self.petnames.word_finder(words, separator)
+    }
+}
+
+impl Indexed for Profiled<'_> {
+    fn cardinality(&self, words: u8) -> u128 {
+        self.petnames.cardinality(words)
+    }
+
+    fn entropy_bits(&self, words: u8) -> f64 {
+        Indexed::entropy_bits(&self.petnames, words)
+    }
+
+    fn generate_at_into(&self, buf: &mut String, index: u128, words: u8, separator: &str) {
+        self.petnames.generate_at_into(buf, index, words, separator);
+    }
+
+    fn index_of(&self, name: &str, words: u8, separator: &str) -> Option<u128> {
+        self.petnames.index_of(name, words, separator)
+    }
+
+    fn indices_of(&self, names: &[&str], words: u8, separator: &str) -> Vec<Vec<u128>> {
+        self.petnames.indices_of(names, words, separator)
+    }
+}
+
+impl Lengths for Profiled<'_> {
+    fn by_letters(&self, words: u8, max_letters: usize) -> Box<dyn ByLetters + '_> {
+        self.petnames.by_letters(words, max_letters)
+    }
+}
+
+/// The reason that a [`Profile`] could not be found or kept to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProfileError {
+    /// There is no profile of this name.
+    Unknown(String),
+    /// The word lists have no nouns that this profile allows.
+    NoWords(Profile),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "unknown profile: {name}"),
+            Self::NoWords(profile) => {
+                write!(f, "no nouns in the word lists are valid with the {profile} profile")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ProfileError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Profile, ProfileError};
+    use crate::{Alphabet, Suffix};
+
+    #[test]
+    fn profiles_parse_by_name() {
+        for profile in Profile::ALL {
+            assert_eq!(profile.name().parse(), Ok(profile));
+        }
+        assert_eq!("dns".parse::<Profile>(), Err(ProfileError::Unknown("dns".into())));
+    }
+
+    #[test]
+    fn allows_word_checks_characters_and_reserved_words() {
+        assert!(Profile::DnsLabel.allows_word("well-known"));
+        assert!(!Profile::DnsLabel.allows_word("9lives"));
+        assert!(Profile::K8sName.allows_word("9lives"));
+        assert!(!Profile::K8sName.allows_word("trailing-"));
+        assert!(!Profile::DnsLabel.allows_word("café"));
+        assert!(Profile::Filename.allows_word("cone"));
+        assert!(!Profile::Filename.allows_word("Con"));
+        assert!(!Profile::Filename.allows_word("nul.txt"));
+        assert!(!Profile::Filename.allows_word(".hidden"));
+        assert!(!Profile::RustIdent.allows_word("type"));
+        assert!(Profile::PythonIdent.allows_word("type"));
+        assert!(!Profile::PythonIdent.allows_word("lambda"));
+        assert!(!Profile::EnvVar.allows_word("path"));
+        assert!(!Profile::EnvVar.allows_word("well-known"));
+        assert!(!Profile::RustIdent.allows_word(""));
+    }
+
+    #[test]
+    fn allows_suffix_keeps_environment_variables_upper_case() {
+        assert!(Profile::EnvVar.allows_suffix(Suffix::new(Alphabet::Digits, 4)));
+        assert!(!Profile::EnvVar.allows_suffix(Suffix::new(Alphabet::Alphanumeric, 4)));
+        assert!(Profile::DnsLabel.allows_suffix(Suffix::new(Alphabet::Consonants, 4)));
+    }
+}
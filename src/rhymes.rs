@@ -0,0 +1,159 @@
+//! Word lists prepared for rhyming, as [`Alliterations`] are for
+//! alliteration.
+//!
+//! Words are grouped by a _rhyme key_: the last vowel sound of the word and
+//! the consonants after it, as in `c-at` and `h-at`. When that last syllable
+//! is weak, as in `mell-ow` or `sill-y`, the syllable before it is part of the
+//! key too, so `mellow` rhymes with `yellow` but not with `window`. This is a
+//! heuristic for English spelling, not pronunciation: `cough` and `bough` share
+//! a key, and `bear` and `hair` do not.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::Alliterations;
+
+/// Final syllables that are seldom stressed in English, after which the
+/// syllable before is needed to make a rhyme.
+const WEAK_ENDINGS: &[&str] = &["a", "e", "el", "en", "er", "ey", "ie", "o", "on", "ow", "y"];
+
+/// The rhyme key of an English `word`: words with the same key rhyme.
+///
+/// Letters are compared in lower case, doubled consonants count once, and
+/// anything that is not a letter is ignored. Letters other than A to Z are
+/// treated as consonants.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(petname::rhyme_key("mellow"), petname::rhyme_key("Yellow"));
+/// assert_eq!(petname::rhyme_key("silly"), petname::rhyme_key("lily"));
+/// assert_ne!(petname::rhyme_key("mellow"), petname::rhyme_key("window"));
+/// ```
+///
+pub fn rhyme_key(word: &str) -> String {
+    let letters: Vec<char> =
+        word.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect();
+    let is_vowel = |position: usize| match letters[position] {
+        'a' | 'e' | 'i' | 'o' | 'u' => true,
+        // As in "silly" or "rhythm", but not "yellow".
+        'y' => !letters.get(position + 1).is_some_and(|&next| is_plain_vowel(next)),
+        // As in "mellow" or "new", but not "wax".
+        'w' => position > 0 && is_plain_vowel(letters[position - 1]),
+        _ => false,
+    };
+    // The start of the last vowel group, and of the one before it.
+    let starts = (0..letters.len())
+        .filter(|&position| is_vowel(position) && (position == 0 || !is_vowel(position - 1)));
+    let (mut last, mut before) = (None, None);
+    for start in starts {
+        (before, last) = (last, Some(start));
+    }
+
+    let start = match (last, before) {
+        (Some(last), Some(before))
+            if WEAK_ENDINGS.contains(&String::from_iter(&letters[last..]).as_str()) =>
+        {
+            before
+        }
+        (Some(last), _) => last,
+        (None, _) => 0,
+    };
+    let mut key = String::new();
+    for (position, &letter) in letters.iter().enumerate().skip(start) {
+        if position > start && letter == letters[position - 1] && !is_vowel(position) {
+            continue;
+        }
+        key.push(letter);
+    }
+    key
+}
+
+fn is_plain_vowel(letter: char) -> bool {
+    matches!(letter, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Word lists prepared for rhyming.
+///
+/// This is an [`Alliterations`] grouped by [`rhyme_key`]: in each group, all
+/// of the nouns, adverbs, and adjectives share a rhyme key, so a name generated
+/// from any of them will be a rhyme, like `mellow-yellow-fellow`. Construct one
+/// with [`Alliterations::group_by`].
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "default-rng")] {
+/// let petnames = petname::Petnames::new("mellow silly yellow", "chilly", "fellow lily");
+/// let mut rhymes = petname::Rhymes::group_by(petnames, petname::rhyme_key);
+/// assert_eq!(rhymes.cardinality(2), 3);
+/// // Only one group has an adverb, so only it can make names of three words.
+/// rhymes.retain(|_, group| group.cardinality(3) > 0);
+/// let name = rhymes.namer(3, "-").iter(&mut rand::rng()).next().expect("no names");
+/// assert_eq!(name, "chilly-silly-lily");
+/// # }
+/// ```
+///
+pub type Rhymes<'a> = Alliterations<'a, String>;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use rand::SeedableRng;
+
+    use super::{rhyme_key, Rhymes};
+    use crate::{Indexed, Petnames};
+
+    #[test]
+    fn rhyme_key_matches_rhymes() {
+        for words in [
+            ["mellow", "yellow", "fellow"],
+            ["silly", "chilly", "lily"],
+            ["cat", "hat", "splat"],
+            ["bake", "cake", "lake"],
+            ["flower", "shower", "tower"],
+            ["cow", "how", "now"],
+        ] {
+            let keys: [String; 3] = words.map(rhyme_key);
+            assert!(keys.iter().all(|key| *key == keys[0]), "{words:?} gave {keys:?}");
+        }
+    }
+
+    #[test]
+    fn rhyme_key_distinguishes_other_words() {
+        for (a, b) in [("mellow", "window"), ("silly", "sully"), ("cat", "cap"), ("happy", "hippy")] {
+            assert_ne!(rhyme_key(a), rhyme_key(b), "{a} and {b}");
+        }
+    }
+
+    #[test]
+    fn rhyme_key_edge_cases() {
+        assert_eq!(rhyme_key("mellow"), "elow");
+        assert_eq!(rhyme_key("Fox"), "ox");
+        assert_eq!(rhyme_key("bee"), "ee");
+        assert_eq!(rhyme_key("rhythm"), "ythm");
+        assert_eq!(rhyme_key("o'clock"), "ock");
+        assert_eq!(rhyme_key("nth"), "nth");
+        assert_eq!(rhyme_key(""), "");
+    }
+
+    #[test]
+    fn rhymes_group_words_and_round_trip() {
+        let petnames = Petnames::new("mellow silly yellow", "chilly", "cat fellow lily");
+        let mut rhymes = Rhymes::group_by(petnames, rhyme_key);
+        let keys: Vec<String> = rhymes.groups().map(|(key, _)| key).collect();
+        assert_eq!(keys, ["at", "elow", "ily"]);
+        assert_eq!(rhymes.cardinality(2), 2 + 1);
+        assert_eq!(rhymes.cardinality(3), 1);
+        rhymes.retain(|key, _| key != "at");
+        for index in 0..rhymes.cardinality(2) {
+            let name = rhymes.name_at(index, 2, "-").expect("no name");
+            assert_eq!(rhymes.index_of(&name, 2, "-"), Some(index), "{name}");
+        }
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(42);
+        for name in rhymes.namer(2, "-").iter(&mut rng).take(10) {
+            let (first, second) = name.split_once('-').expect("no separator");
+            assert_eq!(rhyme_key(first), rhyme_key(second));
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! Describe the names that a generator can produce, for `petname stats`.
+
+use std::io;
+
+use petname::lang::english::Distinct;
+use petname::{Alliterations, Blocking, Indexed, Namer, Petnames, Suffix, Template, Weighted};
+
+/// The chances of a collision at which to report the number of names.
+const COLLISION_CHANCES: [f64; 2] = [0.5, 0.01];
+
+/// The number of words in each list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sizes {
+    pub adverbs: usize,
+    pub adjectives: usize,
+    pub nouns: usize,
+}
+
+impl std::ops::Add for Sizes {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            adverbs: self.adverbs + other.adverbs,
+            adjectives: self.adjectives + other.adjectives,
+            nouns: self.nouns + other.nouns,
+        }
+    }
+}
+
+/// A generator whose word lists can be described.
+pub trait Describe: Indexed {
+    /// The number of words in each list.
+    fn sizes(&self) -> Sizes;
+
+    /// The number of words in each list of each alliterative group, by first
+    /// letter. Empty for generators that do not alliterate.
+    fn groups(&self) -> Vec<(char, Sizes)> {
+        Vec::new()
+    }
+}
+
+impl Describe for Petnames<'_> {
+    fn sizes(&self) -> Sizes {
+        Sizes { adverbs: self.adverbs.len(), adjectives: self.adjectives.len(), nouns: self.nouns.len() }
+    }
+}
+
+impl Describe for Alliterations<'_> {
+    fn sizes(&self) -> Sizes {
+        group_sizes(self)
+    }
+
+    fn groups(&self) -> Vec<(char, Sizes)> {
+        Alliterations::groups(self).map(|(first_letter, group)| (first_letter, group.sizes())).collect()
+    }
+}
+
+/// Groups by a key other than a letter are described only in total.
+impl Describe for Alliterations<'_, String> {
+    fn sizes(&self) -> Sizes {
+        group_sizes(self)
+    }
+}
+
+impl Describe for Alliterations<'_, crate::cli::GroupKey> {
+    fn sizes(&self) -> Sizes {
+        group_sizes(self)
+    }
+}
+
+/// The number of words in each list, across all groups.
+fn group_sizes<K: Ord + Clone>(alliterations: &Alliterations<'_, K>) -> Sizes {
+    alliterations.groups().map(|(_, group)| group.sizes()).fold(Sizes::default(), |a, b| a + b)
+}
+
+impl Describe for Distinct<'_, '_> {
+    fn sizes(&self) -> Sizes {
+        self.petnames().sizes()
+    }
+}
+
+impl Describe for Blocking<'_, '_> {
+    fn sizes(&self) -> Sizes {
+        self.petnames().sizes()
+    }
+}
+
+impl Describe for Template<'_, '_> {
+    fn sizes(&self) -> Sizes {
+        self.petnames().sizes()
+    }
+}
+
+#[cfg(feature = "lang-turkish")]
+impl Describe for petname::lang::turkish::Petnames<'_> {
+    fn sizes(&self) -> Sizes {
+        Sizes { adverbs: self.adverbs.len(), adjectives: self.adjectives.len(), nouns: self.nouns.len() }
+    }
+}
+
+/// The name space for one number of words.
+struct Row {
+    words: u8,
+    cardinality: u128,
+    entropy_bits: f64,
+    /// The number of names, chosen at random, for each of
+    /// [`COLLISION_CHANCES`] of a collision.
+    names_before_collision: [f64; COLLISION_CHANCES.len()],
+}
+
+impl Row {
+    fn new<GEN: Describe>(namer: &Namer<GEN>) -> Self {
+        let entropy_bits = namer.entropy_bits();
+        Self::with_bits(namer.words(), namer.cardinality(), entropy_bits, entropy_bits)
+    }
+
+    /// The name space for names with `entropy_bits` of entropy, and
+    /// `collision_bits` of collision entropy, which are the same when every
+    /// name is equally likely.
+    fn with_bits(words: u8, cardinality: u128, entropy_bits: f64, collision_bits: f64) -> Self {
+        Self {
+            words,
+            cardinality,
+            entropy_bits,
+            names_before_collision: COLLISION_CHANCES.map(|chance| match cardinality {
+                0 => 0.0,
+                // The birthday approximation: sqrt(2N ln(1 / (1 - p))).
+                _ => (2.0 * -(1.0 - chance).ln()).sqrt() * (collision_bits / 2.0).exp2(),
+            }),
+        }
+    }
+}
+
+/// Write statistics for names of 1 to `max_words` words, with `suffix`
+/// appended if given, as plain text or as JSON. `removed` is the number of
+/// words removed for sounding like another, if they were.
+pub fn write<OUT, GEN>(
+    writer: &mut OUT,
+    generator: &GEN,
+    max_words: u8,
+    suffix: Option<Suffix>,
+    removed: Option<usize>,
+    json: bool,
+) -> io::Result<()>
+where
+    OUT: io::Write,
+    GEN: Describe,
+{
+    let sizes = generator.sizes();
+    let rows: Vec<Row> = (1..=max_words)
+        .map(|words| {
+            let mut namer = Namer::new(generator, words, "");
+            if let Some(suffix) = suffix {
+                namer = namer.with_suffix(suffix);
+            }
+            Row::new(&namer)
+        })
+        .collect();
+    let groups = generator.groups();
+    if json {
+        write_json(writer, sizes, removed, &rows, &groups, false)
+    } else {
+        write_text(writer, sizes, removed, &rows, &groups, false)
+    }
+}
+
+/// Write statistics as [`write`] does, but for names whose words are chosen
+/// by weight. The entropy is then their effective entropy, and collisions are
+/// estimated from their collision entropy.
+pub fn write_weighted<OUT: io::Write>(
+    writer: &mut OUT,
+    weighted: &Weighted,
+    max_words: u8,
+    suffix: Option<Suffix>,
+    json: bool,
+) -> io::Result<()> {
+    let sizes = weighted.petnames().sizes();
+    let (suffixes, suffix_bits) =
+        suffix.map_or((1, 0.0), |suffix| (suffix.cardinality(), suffix.entropy_bits()));
+    let rows: Vec<Row> = (1..=max_words)
+        .map(|words| match weighted.petnames().cardinality(words) {
+            0 => Row::with_bits(words, 0, 0.0, 0.0),
+            cardinality => Row::with_bits(
+                words,
+                cardinality.saturating_mul(suffixes),
+                weighted.entropy_bits(words) + suffix_bits,
+                weighted.collision_bits(words) + suffix_bits,
+            ),
+        })
+        .collect();
+    if json {
+        write_json(writer, sizes, None, &rows, &[], true)
+    } else {
+        write_text(writer, sizes, None, &rows, &[], true)
+    }
+}
+
+fn write_text<OUT: io::Write>(
+    writer: &mut OUT,
+    sizes: Sizes,
+    removed: Option<usize>,
+    rows: &[Row],
+    groups: &[(char, Sizes)],
+    weighted: bool,
+) -> io::Result<()> {
+    writeln!(writer, "adverbs: {}", sizes.adverbs)?;
+    writeln!(writer, "adjectives: {}", sizes.adjectives)?;
+    writeln!(writer, "nouns: {}", sizes.nouns)?;
+    if let Some(removed) = removed {
+        writeln!(writer, "removed as sounding alike: {removed}")?;
+    }
+    writeln!(writer)?;
+
+    let entropy = if weighted { "effective entropy (bits)" } else { "entropy (bits)" };
+    let mut table = vec![["words", "names", entropy, "50% collision after", "1% collision after"]
+        .map(String::from)
+        .to_vec()];
+    table.extend(rows.iter().map(|row| {
+        let mut cells = vec![row.words.to_string(), row.cardinality.to_string()];
+        cells.push(format!("{:.2}", row.entropy_bits));
+        cells.extend(row.names_before_collision.iter().map(|names| format!("{names:.0}")));
+        cells
+    }));
+    write_table(writer, &table)?;
+
+    if !groups.is_empty() {
+        writeln!(writer)?;
+        let mut table = vec![["letter", "adverbs", "adjectives", "nouns"].map(String::from).to_vec()];
+        table.extend(groups.iter().map(|(first_letter, sizes)| {
+            vec![
+                first_letter.to_string(),
+                sizes.adverbs.to_string(),
+                sizes.adjectives.to_string(),
+                sizes.nouns.to_string(),
+            ]
+        }));
+        write_table(writer, &table)?;
+    }
+
+    Ok(())
+}
+
+/// Write `table` with its columns aligned. The first row is the header.
+fn write_table<OUT: io::Write>(writer: &mut OUT, table: &[Vec<String>]) -> io::Result<()> {
+    let widths: Vec<usize> = (0..table[0].len())
+        .map(|column| table.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect();
+    for row in table {
+        let line: Vec<String> =
+            row.iter().zip(&widths).map(|(cell, &width)| format!("{cell:<width$}")).collect();
+        writeln!(writer, "{}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+fn write_json<OUT: io::Write>(
+    writer: &mut OUT,
+    sizes: Sizes,
+    removed: Option<usize>,
+    rows: &[Row],
+    groups: &[(char, Sizes)],
+    weighted: bool,
+) -> io::Result<()> {
+    write!(writer, "{{\"lists\":{}", json_sizes(sizes))?;
+    if let Some(removed) = removed {
+        write!(writer, ",\"removed_as_sounding_alike\":{removed}")?;
+    }
+    if weighted {
+        write!(writer, ",\"weighted\":true")?;
+    }
+    write!(writer, ",\"names\":[")?;
+    for (position, row) in rows.iter().enumerate() {
+        if position > 0 {
+            write!(writer, ",")?;
+        }
+        // The cardinality is written as a number, however large; JSON does
+        // not limit the precision of numbers, even if some parsers do.
+        write!(
+            writer,
+            "{{\"words\":{},\"cardinality\":{},\"entropy_bits\":{}",
+            row.words,
+            row.cardinality,
+            json_number(row.entropy_bits),
+        )?;
+        write!(writer, ",\"names_before_collision\":{{")?;
+        for (position, (chance, names)) in
+            COLLISION_CHANCES.iter().zip(row.names_before_collision).enumerate()
+        {
+            if position > 0 {
+                write!(writer, ",")?;
+            }
+            write!(writer, "\"{chance}\":{}", json_number(names))?;
+        }
+        write!(writer, "}}}}")?;
+    }
+    write!(writer, "],\"groups\":[")?;
+    for (position, (first_letter, sizes)) in groups.iter().enumerate() {
+        if position > 0 {
+            write!(writer, ",")?;
+        }
+        write!(writer, "{{\"letter\":{},\"lists\":{}}}", json_string(*first_letter), json_sizes(*sizes))?;
+    }
+    writeln!(writer, "]}}")
+}
+
+fn json_sizes(sizes: Sizes) -> String {
+    format!("{{\"adverbs\":{},\"adjectives\":{},\"nouns\":{}}}", sizes.adverbs, sizes.adjectives, sizes.nouns)
+}
+
+/// JSON has no infinity or NaN, so these are written as `null`.
+fn json_number(number: f64) -> String {
+    if number.is_finite() {
+        number.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn json_string(letter: char) -> String {
+    match letter {
+        '"' => "\"\\\"\"".to_string(),
+        '\\' => "\"\\\\\"".to_string(),
+        letter if letter.is_control() => format!("\"\\u{:04x}\"", letter as u32),
+        letter => format!("\"{letter}\""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petname::{Alliterations, Petnames};
+
+    use super::{json_string, write};
+
+    fn capture(json: bool) -> String {
+        let alliterations: Alliterations = Petnames::new("able apt bold", "away", "ant bee").into();
+        let mut out = Vec::new();
+        write(&mut out, &alliterations, 2, None, None, json).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn stats_as_text() {
+        assert_eq!(
+            capture(false),
+            concat!(
+                "adverbs: 1\n",
+                "adjectives: 3\n",
+                "nouns: 2\n",
+                "\n",
+                "words  names  entropy (bits)  50% collision after  1% collision after\n",
+                "1      2      1.00            2                    0\n",
+                "2      3      1.58            2                    0\n",
+                "\n",
+                "letter  adverbs  adjectives  nouns\n",
+                "a       1        2           1\n",
+                "b       0        1           1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn stats_as_json() {
+        let json = capture(true);
+        assert!(json.starts_with(concat!(
+            r#"{"lists":{"adverbs":1,"adjectives":3,"nouns":2},"#,
+            r#""names":[{"words":1,"cardinality":2,"entropy_bits":1,"names_before_collision":{"0.5":"#,
+        )));
+        assert!(json.ends_with(concat!(
+            r#""groups":[{"letter":"a","lists":{"adverbs":1,"adjectives":2,"nouns":1}},"#,
+            r#"{"letter":"b","lists":{"adverbs":0,"adjectives":1,"nouns":1}}]}"#,
+            "\n",
+        )));
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        assert_eq!(json_string('a'), r#""a""#);
+        assert_eq!(json_string('"'), r#""\"""#);
+        assert_eq!(json_string('\\'), r#""\\""#);
+        assert_eq!(json_string('\n'), r#""\u000a""#);
+    }
+}
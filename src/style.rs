@@ -0,0 +1,176 @@
+//! Case styles for names, as applied by [`Namer::with_style`].
+//!
+//! [`Namer::with_style`]: crate::Namer::with_style
+
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::Generator;
+
+/// How to change the case of each word in a name.
+///
+/// Case is changed according to the rules of the generator's language – see
+/// [`Generator::uppercase_into`] – so, for example, Turkish `i` becomes `İ`
+/// in upper case, not `I`.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// `every word in lower case`.
+    Lower,
+    /// `EVERY WORD IN UPPER CASE`.
+    Upper,
+    /// `Every Word Capitalised`.
+    Title,
+    /// `firstWordInLowerCaseThenCapitalised`; conventionally unseparated.
+    Camel,
+    /// `EveryWordCapitalised`; conventionally unseparated.
+    Pascal,
+    /// `snake_case`: lower case, conventionally separated by `_`.
+    Snake,
+    /// `kebab-case`: lower case, conventionally separated by `-`.
+    Kebab,
+    /// `SCREAMING_SNAKE_CASE`: upper case, conventionally separated by `_`.
+    ScreamingSnake,
+}
+
+impl Style {
+    /// The separator conventionally used between words in this style.
+    ///
+    /// A [`Namer`][`crate::Namer`] always uses the separator it was given;
+    /// this is for choosing that separator.
+    pub fn separator(self) -> &'static str {
+        match self {
+            Self::Lower | Self::Upper | Self::Title | Self::Kebab => "-",
+            Self::Camel | Self::Pascal => "",
+            Self::Snake | Self::ScreamingSnake => "_",
+        }
+    }
+
+    /// Push `name` onto `buf` in this style. Each of `words`, as ranges of
+    /// bytes in `name`, is restyled; everything between them is pushed as it
+    /// is.
+    pub(crate) fn restyle_into<G>(self, generator: &G, buf: &mut String, name: &str, words: &[Range<usize>])
+    where
+        G: Generator + ?Sized,
+    {
+        let mut end = 0;
+        for (position, range) in words.iter().enumerate() {
+            buf.push_str(&name[end..range.start]);
+            end = range.end;
+            let word = &name[range.clone()];
+            match self {
+                Self::Lower | Self::Snake | Self::Kebab => generator.lowercase_into(buf, word),
+                Self::Upper | Self::ScreamingSnake => generator.uppercase_into(buf, word),
+                Self::Camel if position == 0 => generator.lowercase_into(buf, word),
+                Self::Title | Self::Camel | Self::Pascal => {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        generator.uppercase_into(buf, first.encode_utf8(&mut [0; 4]));
+                        generator.lowercase_into(buf, chars.as_str());
+                    }
+                }
+            }
+        }
+        buf.push_str(&name[end..]);
+    }
+}
+
+/// The words of a name whose words are `lengths` bytes long, joined by
+/// `separator`, as ranges of bytes in that name.
+pub(crate) fn joined_ranges(lengths: impl IntoIterator<Item = usize>, separator: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    lengths
+        .into_iter()
+        .map(|length| {
+            let range = start..start + length;
+            start = range.end + separator.len();
+            range
+        })
+        .collect()
+}
+
+/// The words of `name`, split on `separator`, as ranges of bytes in `name`.
+/// Without a separator, `name` is one word.
+pub(crate) fn split_ranges(name: &str, separator: &str) -> Vec<Range<usize>> {
+    if separator.is_empty() {
+        core::iter::once(0..name.len()).collect()
+    } else {
+        joined_ranges(name.split(separator).map(str::len), separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeSet, string::String};
+
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::{joined_ranges, split_ranges, Style};
+    use crate::{Alliterations, Grammar, Indexed, Petnames};
+
+    fn restyle(style: Style, words: &[&str]) -> String {
+        let mut buf = String::new();
+        let name = words.join(style.separator());
+        let ranges = joined_ranges(words.iter().map(|word| word.len()), style.separator());
+        style.restyle_into(&Petnames::new("", "", ""), &mut buf, &name, &ranges);
+        buf
+    }
+
+    #[test]
+    fn restyle_into_changes_case_of_each_word() {
+        let words = ["very", "ünique", "cat"];
+        assert_eq!(restyle(Style::Lower, &words), "very-ünique-cat");
+        assert_eq!(restyle(Style::Upper, &words), "VERY-ÜNIQUE-CAT");
+        assert_eq!(restyle(Style::Title, &words), "Very-Ünique-Cat");
+        assert_eq!(restyle(Style::Camel, &words), "veryÜniqueCat");
+        assert_eq!(restyle(Style::Pascal, &words), "VeryÜniqueCat");
+        assert_eq!(restyle(Style::Snake, &words), "very_ünique_cat");
+        assert_eq!(restyle(Style::Kebab, &words), "very-ünique-cat");
+        assert_eq!(restyle(Style::ScreamingSnake, &words), "VERY_ÜNIQUE_CAT");
+    }
+
+    #[test]
+    fn restyle_into_handles_empty_words() {
+        assert_eq!(restyle(Style::Pascal, &[""]), "");
+        assert_eq!(restyle(Style::Title, &["", "cat"]), "-Cat");
+    }
+
+    #[test]
+    fn restyle_into_leaves_text_between_words() {
+        let mut buf = String::new();
+        let ranges = split_ranges("very-ünique-cat", "-");
+        Style::Pascal.restyle_into(&Petnames::new("", "", ""), &mut buf, "very-ünique-cat", &ranges[1..]);
+        assert_eq!(buf, "very-Ünique-Cat");
+        assert_eq!(split_ranges("big-cat", ""), core::iter::once(0..7).collect::<alloc::vec::Vec<_>>());
+    }
+
+    #[test]
+    fn word_finder_finds_the_same_words_as_word_ranges() {
+        fn check<G: Indexed>(generator: &G, separator: &str) {
+            for words in 1..=3 {
+                let word_finder = generator.word_finder(words, separator);
+                for index in 0..generator.cardinality(words) {
+                    let name = generator.name_at(index, words, separator).unwrap();
+                    assert_eq!(
+                        word_finder(&name),
+                        generator.word_ranges(&name, words, separator),
+                        "{name:?}"
+                    );
+                }
+            }
+        }
+        // Without a separator, or with one in a word, words must be looked up.
+        let petnames = Petnames::new("red reddish", "very", "ant antler x-ray");
+        let alliterations = Alliterations::group_by(petnames.clone(), |word| word.len());
+        let grammar = Grammar::from(petnames.clone());
+        for separator in ["", "-", "_"] {
+            check(&petnames, separator);
+            check(&alliterations, separator);
+            check(&grammar, separator);
+        }
+        let namer = petnames.namer(2, "").with_style(Style::Pascal);
+        let names: BTreeSet<String> = namer.unique_iter(&mut SmallRng::seed_from_u64(0)).collect();
+        let expected = ["RedAnt", "RedAntler", "RedX-ray", "ReddishAnt", "ReddishAntler", "ReddishX-ray"];
+        assert_eq!(names, expected.map(String::from).into());
+    }
+}
@@ -0,0 +1,149 @@
+//! Random suffixes for names, as added by [`Namer::with_suffix`].
+//!
+//! [`Namer::with_suffix`]: crate::Namer::with_suffix
+
+use alloc::string::String;
+
+use rand::RngExt;
+
+use crate::log2;
+
+/// The characters that a [`Suffix`] is made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// Decimal digits, `0`–`9`.
+    Digits,
+    /// Decimal digits and lower-case ASCII letters, `0`–`9` and `a`–`z`.
+    Alphanumeric,
+    /// Lower-case ASCII consonants, leaving out `y`, so that a suffix cannot
+    /// spell a word by accident.
+    Consonants,
+}
+
+impl Alphabet {
+    /// The characters in this alphabet, in order.
+    pub fn chars(self) -> &'static str {
+        match self {
+            Self::Digits => "0123456789",
+            Self::Alphanumeric => "0123456789abcdefghijklmnopqrstuvwxyz",
+            Self::Consonants => "bcdfghjklmnpqrstvwxz",
+        }
+    }
+}
+
+/// A run of random characters appended to each name, e.g. the `4821` in
+/// `happy-otter-4821`, for extra entropy.
+///
+/// # Examples
+///
+/// ```rust
+/// use petname::{Alphabet, Suffix};
+/// let suffix = Suffix::new(Alphabet::Digits, 4);
+/// assert_eq!(suffix.cardinality(), 10_000);
+/// # #[cfg(feature = "default-rng")] {
+/// let petnames = petname::Petnames::new("happy", "", "otter");
+/// let name = petnames.namer(2, "-").with_suffix(suffix).iter(&mut rand::rng()).next().unwrap();
+/// assert!(name.starts_with("happy-otter-"));
+/// assert_eq!(name.len(), "happy-otter-".len() + 4);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suffix {
+    alphabet: Alphabet,
+    length: u8,
+}
+
+impl Suffix {
+    /// A suffix of `length` characters from `alphabet`.
+    pub fn new(alphabet: Alphabet, length: u8) -> Self {
+        Self { alphabet, length }
+    }
+
+    /// The characters that this suffix is made of.
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    /// The number of characters in this suffix.
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+
+    /// Calculate the number of distinct suffixes.
+    ///
+    /// This can saturate. If there are more than `u128::MAX` suffixes then
+    /// this will return `u128::MAX`.
+    pub fn cardinality(&self) -> u128 {
+        (self.alphabet.chars().len() as u128).saturating_pow(self.length.into())
+    }
+
+    /// The entropy, in bits, of a suffix chosen uniformly.
+    ///
+    /// Like [`Indexed::entropy_bits`][`crate::Indexed::entropy_bits`], this
+    /// does not saturate when the cardinality does.
+    pub fn entropy_bits(&self) -> f64 {
+        f64::from(self.length) * log2(self.alphabet.chars().len() as f64)
+    }
+
+    /// Whether `suffix` is one of the suffixes that this could generate.
+    pub(crate) fn contains(&self, suffix: &str) -> bool {
+        suffix.chars().count() == usize::from(self.length)
+            && suffix.chars().all(|c| self.alphabet.chars().contains(c))
+    }
+
+    /// Push a random suffix onto `buf`.
+    pub(crate) fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng) {
+        let chars = self.alphabet.chars().as_bytes();
+        for _ in 0..self.length {
+            buf.push(chars[rng.random_range(0..chars.len())] as char);
+        }
+    }
+
+    /// Push the suffix at `index` onto `buf`. The last character varies
+    /// fastest.
+    pub(crate) fn generate_at_into(&self, buf: &mut String, mut index: u128) {
+        let chars = self.alphabet.chars().as_bytes();
+        let radix = chars.len() as u128;
+        let start = buf.len();
+        for _ in 0..self.length {
+            buf.insert(start, chars[(index % radix) as usize] as char);
+            index /= radix;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::{Alphabet, Suffix};
+
+    #[test]
+    fn cardinality_and_entropy() {
+        let suffix = Suffix::new(Alphabet::Alphanumeric, 5);
+        assert_eq!(suffix.cardinality(), 36u128.pow(5));
+        assert_eq!(suffix.entropy_bits(), 5.0 * crate::log2(36.0));
+        assert_eq!(Suffix::new(Alphabet::Consonants, 0).cardinality(), 1);
+        assert_eq!(Suffix::new(Alphabet::Digits, 255).cardinality(), u128::MAX);
+    }
+
+    #[test]
+    fn generate_at_into_counts_up() {
+        let suffix = Suffix::new(Alphabet::Digits, 3);
+        let at = |index| {
+            let mut buf = String::from("cat-");
+            suffix.generate_at_into(&mut buf, index);
+            buf
+        };
+        assert_eq!(at(0), "cat-000");
+        assert_eq!(at(42), "cat-042");
+        assert_eq!(at(999), "cat-999");
+    }
+
+    #[test]
+    fn consonants_contain_no_vowels() {
+        assert!(!Alphabet::Consonants.chars().contains(['a', 'e', 'i', 'o', 'u', 'y']));
+        assert_eq!(Alphabet::Consonants.chars().len(), 20);
+    }
+}
@@ -0,0 +1,412 @@
+//! Names shaped by a pattern of word lists, random characters, and literals.
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, ops::Range};
+
+use rand::{seq::IndexedRandom, RngExt};
+
+use crate::{log2, Generator, Indexed, Namer, Petnames};
+
+/// The most characters that a `{digits:N}`, `{hex:N}`, or `{letters:N}` slot
+/// can have.
+const MAX_CHARS: u8 = 16;
+
+/// A [`Generator`] of names shaped by a pattern, drawing words from a
+/// [`Petnames`].
+///
+/// A pattern is literal text with _slots_ in braces:
+///
+/// - `{adverb}`, `{adjective}`, `{noun}`: a word from that list.
+/// - `{digits:N}`: `N` random decimal digits, e.g. `042`.
+/// - `{hex:N}`: `N` random lower-case hexadecimal digits, e.g. `0f3a`.
+/// - `{letters:N}`: `N` random lower-case ASCII letters, e.g. `qz`.
+///
+/// `N` is between 1 and 16, and can be omitted to mean 1. Write `{{` and `}}`
+/// for literal braces.
+///
+/// Word slots must have literal text between them, e.g. `{adjective}-{noun}`
+/// rather than `{adjective}{noun}`, or different words could join into the
+/// same name – `ab` + `a` and `a` + `ba` – and names would be counted twice.
+///
+/// The pattern alone decides the shape of each name, so the number of words
+/// and the separator given to [`Generator::generate_into`] – and to the
+/// [`Indexed`] methods – are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "default-rng")] {
+/// let petnames = petname::Petnames::new("fair", "", "cat dog");
+/// let template = petname::Template::new(&petnames, "{adjective}-{noun}-{digits:3}").unwrap();
+/// assert_eq!(template.cardinality(), 2000);
+/// let name = template.namer().iter(&mut rand::rng()).next().unwrap();
+/// assert!(name.starts_with("fair-"));
+/// # }
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct Template<'a, 'w> {
+    petnames: &'a Petnames<'w>,
+    slots: Vec<Slot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Slot {
+    Literal(String),
+    Adverb,
+    Adjective,
+    Noun,
+    Chars(Charset, u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Charset {
+    Digits,
+    Hex,
+    Letters,
+}
+
+impl Charset {
+    fn chars(self) -> &'static [u8] {
+        match self {
+            Self::Digits => b"0123456789",
+            Self::Hex => b"0123456789abcdef",
+            Self::Letters => b"abcdefghijklmnopqrstuvwxyz",
+        }
+    }
+}
+
+impl<'a, 'w> Template<'a, 'w> {
+    /// Parse `pattern` into a template that draws words from `petnames`.
+    pub fn new(petnames: &'a Petnames<'w>, pattern: &str) -> Result<Self, TemplateError> {
+        let mut slots = Vec::new();
+        let mut literal = String::new();
+        let mut rest = pattern;
+        while let Some(c) = rest.chars().next() {
+            if let Some(after) = rest.strip_prefix("{{") {
+                literal.push('{');
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("}}") {
+                literal.push('}');
+                rest = after;
+            } else if c == '}' {
+                return Err(TemplateError::Unopened);
+            } else if c == '{' {
+                let (slot, after) = rest[1..].split_once('}').ok_or(TemplateError::Unclosed)?;
+                if !literal.is_empty() {
+                    slots.push(Slot::Literal(core::mem::take(&mut literal)));
+                }
+                slots.push(Slot::parse(slot)?);
+                rest = after;
+            } else {
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        if !literal.is_empty() {
+            slots.push(Slot::Literal(literal));
+        }
+        let mut words = slots.iter().filter(|slot| !matches!(slot, Slot::Chars(..)));
+        let mut previous = words.next();
+        for slot in words {
+            if !matches!(previous, Some(Slot::Literal(_))) && !matches!(slot, Slot::Literal(_)) {
+                return Err(TemplateError::Unseparated);
+            }
+            previous = Some(slot);
+        }
+        Ok(Self { petnames, slots })
+    }
+
+    /// Calculate the number of distinct names that this template can produce.
+    ///
+    /// This can saturate. If the total possible combinations exceeds
+    /// `u128::MAX` then this will return `u128::MAX`.
+    pub fn cardinality(&self) -> u128 {
+        self.radices().into_iter().fold(1, u128::saturating_mul)
+    }
+
+    /// The word lists that this template draws words from.
+    pub fn petnames(&self) -> &'a Petnames<'w> {
+        self.petnames
+    }
+
+    /// Create a [`Namer`] that generates names from this template.
+    pub fn namer(&self) -> Namer<'_, Self> {
+        Namer::new(self, 0, "")
+    }
+
+    /// The number of choices for each slot.
+    fn radices(&self) -> Vec<u128> {
+        self.slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Literal(_) => 1,
+                Slot::Adverb => self.petnames.adverbs.len() as u128,
+                Slot::Adjective => self.petnames.adjectives.len() as u128,
+                Slot::Noun => self.petnames.nouns.len() as u128,
+                Slot::Chars(charset, count) => (charset.chars().len() as u128).pow((*count).into()),
+            })
+            .collect()
+    }
+
+    /// The words that a list slot draws from, if it is one.
+    fn list(&self, slot: &Slot) -> Option<&[&'w str]> {
+        match slot {
+            Slot::Adverb => Some(&self.petnames.adverbs),
+            Slot::Adjective => Some(&self.petnames.adjectives),
+            Slot::Noun => Some(&self.petnames.nouns),
+            Slot::Literal(_) | Slot::Chars(..) => None,
+        }
+    }
+
+    /// Find the choice made for each of `slots` to produce `name`, if any.
+    fn split(&self, name: &str, slots: &[Slot]) -> Option<Vec<u128>> {
+        let Some((slot, rest)) = slots.split_first() else {
+            return name.is_empty().then(Vec::new);
+        };
+        let found = |choice: u128, rest_of_name: &str| {
+            let mut choices = self.split(rest_of_name, rest)?;
+            choices.insert(0, choice);
+            Some(choices)
+        };
+        match slot {
+            Slot::Literal(literal) => found(0, name.strip_prefix(literal.as_str())?),
+            Slot::Chars(charset, count) => {
+                let count = usize::from(*count);
+                let chars = name.as_bytes().get(..count)?;
+                let choice = chars.iter().try_fold(0u128, |choice, byte| {
+                    let digit = charset.chars().iter().position(|c| c == byte)?;
+                    Some(choice * charset.chars().len() as u128 + digit as u128)
+                })?;
+                found(choice, &name[count..])
+            }
+            list => self
+                .list(list)?
+                .iter()
+                .enumerate()
+                .find_map(|(position, word)| found(position as u128, name.strip_prefix(word)?)),
+        }
+    }
+}
+
+impl Slot {
+    fn parse(slot: &str) -> Result<Self, TemplateError> {
+        let (kind, count) = match slot.split_once(':') {
+            Some((kind, count)) => (kind, Some(count)),
+            None => (slot, None),
+        };
+        let charset = match kind {
+            "adverb" | "adjective" | "noun" if count.is_some() => {
+                return Err(TemplateError::Count(slot.into()));
+            }
+            "adverb" => return Ok(Self::Adverb),
+            "adjective" => return Ok(Self::Adjective),
+            "noun" => return Ok(Self::Noun),
+            "digits" => Charset::Digits,
+            "hex" => Charset::Hex,
+            "letters" => Charset::Letters,
+            _ => return Err(TemplateError::UnknownSlot(slot.into())),
+        };
+        match count.map(str::parse::<u8>) {
+            None => Ok(Self::Chars(charset, 1)),
+            Some(Ok(count @ 1..=MAX_CHARS)) => Ok(Self::Chars(charset, count)),
+            Some(_) => Err(TemplateError::Count(slot.into())),
+        }
+    }
+}
+
+impl Generator for Template<'_, '_> {
+    fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, _words: u8, _separator: &str) {
+        if self.cardinality() == 0 {
+            return;
+        }
+        for slot in &self.slots {
+            match slot {
+                Slot::Literal(literal) => buf.push_str(literal),
+                Slot::Chars(charset, count) => {
+                    for _ in 0..*count {
+                        buf.push(char::from(charset.chars()[rng.random_range(0..charset.chars().len())]));
+                    }
+                }
+                list => {
+                    if let Some(word) = self.list(list).and_then(|list| list.choose(rng)) {
+                        buf.push_str(word);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Each slot but a literal is a word; literals are left as they are. A
+    /// name that this template cannot have produced is one word.
+    fn word_ranges(&self, name: &str, _words: u8, _separator: &str) -> Vec<Range<usize>> {
+        let Some(choices) = self.split(name, &self.slots) else {
+            return crate::style::split_ranges(name, "");
+        };
+        let mut ranges = Vec::with_capacity(self.slots.len());
+        let mut start = 0;
+        for (slot, choice) in self.slots.iter().zip(choices) {
+            let length = match slot {
+                Slot::Literal(literal) => literal.len(),
+                Slot::Chars(_, count) => usize::from(*count),
+                list => self.list(list).map_or(0, |list| list[choice as usize].len()),
+            };
+            if !matches!(slot, Slot::Literal(_)) {
+                ranges.push(start..start + length);
+            }
+            start += length;
+        }
+        ranges
+    }
+}
+
+impl Indexed for Template<'_, '_> {
+    fn cardinality(&self, _words: u8) -> u128 {
+        Template::cardinality(self)
+    }
+
+    /// The sum of the entropy of each slot, which does not saturate.
+    fn entropy_bits(&self, _words: u8) -> f64 {
+        match self.cardinality() {
+            0 => 0.0,
+            _ => self.radices().into_iter().map(|radix| log2(radix as f64)).sum(),
+        }
+    }
+
+    /// Slots vary from last to first, i.e. the last slot is the least
+    /// significant.
+    fn generate_at_into(&self, buf: &mut String, index: u128, _words: u8, _separator: &str) {
+        if index >= self.cardinality() {
+            return;
+        }
+        let radices = self.radices();
+        let mut choices = alloc::vec![0; radices.len()];
+        let mut index = index;
+        for (choice, radix) in choices.iter_mut().zip(&radices).rev() {
+            *choice = index % radix;
+            index /= radix;
+        }
+        for (slot, mut choice) in self.slots.iter().zip(choices) {
+            match slot {
+                Slot::Literal(literal) => buf.push_str(literal),
+                Slot::Chars(charset, count) => {
+                    let base = charset.chars().len() as u128;
+                    let mut chars = alloc::vec![0u8; usize::from(*count)];
+                    for c in chars.iter_mut().rev() {
+                        *c = charset.chars()[(choice % base) as usize];
+                        choice /= base;
+                    }
+                    chars.into_iter().for_each(|c| buf.push(char::from(c)));
+                }
+                list => {
+                    if let Some(word) = self.list(list).and_then(|list| list.get(choice as usize)) {
+                        buf.push_str(word);
+                    }
+                }
+            }
+        }
+    }
+
+    fn index_of(&self, name: &str, _words: u8, _separator: &str) -> Option<u128> {
+        let choices = self.split(name, &self.slots)?;
+        choices
+            .into_iter()
+            .zip(self.radices())
+            .try_fold(0u128, |index, (choice, radix)| index.checked_mul(radix)?.checked_add(choice))
+    }
+}
+
+/// The reason that [`Template::new`] could not parse a pattern.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateError {
+    /// A `{` has no matching `}`; write `{{` for a literal `{`.
+    Unclosed,
+    /// A `}` has no matching `{`; write `}}` for a literal `}`.
+    Unopened,
+    /// The slot is not one of those described in [`Template`].
+    UnknownSlot(String),
+    /// The number of characters in the slot is not between 1 and 16, or the
+    /// slot does not take a number.
+    Count(String),
+    /// Two word slots have no literal text between them.
+    Unseparated,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unclosed => write!(f, "unclosed {{; use {{{{ for a literal {{"),
+            Self::Unopened => write!(f, "unmatched }}; use }}}} for a literal }}"),
+            Self::UnknownSlot(slot) => write!(f, "unknown slot: {{{slot}}}"),
+            Self::Count(slot) => write!(f, "bad number of characters: {{{slot}}}"),
+            Self::Unseparated => {
+                write!(f, "word slots need literal text between them, e.g. {{adjective}}-{{noun}}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for TemplateError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+    use super::{Template, TemplateError};
+    use crate::{Indexed, Petnames, Style};
+
+    #[test]
+    fn new_parses_slots_and_literals() {
+        let petnames = Petnames::new("able bold", "very", "ant bee cow");
+        let cardinality = |pattern| Template::new(&petnames, pattern).map(|template| template.cardinality());
+        assert_eq!(cardinality("{adjective}-{noun}"), Ok(6));
+        assert_eq!(cardinality("{noun}.{hex:4}"), Ok(3 << 16));
+        assert_eq!(cardinality("{adverb}{letters}{digits:2}"), Ok(26 * 100));
+        assert_eq!(cardinality("{{literal}}"), Ok(1));
+        assert_eq!(cardinality(""), Ok(1));
+        assert_eq!(cardinality("{noun"), Err(TemplateError::Unclosed));
+        assert_eq!(cardinality("noun}"), Err(TemplateError::Unopened));
+        assert_eq!(cardinality("{verb}"), Err(TemplateError::UnknownSlot("verb".into())));
+        assert_eq!(cardinality("{digits:0}"), Err(TemplateError::Count("digits:0".into())));
+        assert_eq!(cardinality("{adjective}{noun}"), Err(TemplateError::Unseparated));
+        assert_eq!(cardinality("{adjective}{hex:2}{noun}"), Err(TemplateError::Unseparated));
+        assert_eq!(cardinality("{adjective}{hex:2}.{noun}"), Ok(6 << 8));
+        assert_eq!(cardinality("{hex:17}"), Err(TemplateError::Count("hex:17".into())));
+        assert_eq!(cardinality("{noun:2}"), Err(TemplateError::Count("noun:2".into())));
+    }
+
+    #[test]
+    fn name_at_and_index_of_are_inverses() {
+        // "bold" is a prefix of "bolder", so splitting must backtrack.
+        let petnames = Petnames::new("bold bolder", "", "er ant");
+        let template = Template::new(&petnames, "{{{adjective}-{noun}}}_{hex:2}").unwrap();
+        let names: Vec<String> =
+            (0..template.cardinality()).map(|index| template.name_at(index, 0, "").unwrap()).collect();
+        assert_eq!(names[0], "{bold-er}_00");
+        assert_eq!(names[255], "{bold-er}_ff");
+        assert_eq!(names.iter().collect::<BTreeSet<_>>().len(), names.len());
+        for (index, name) in names.iter().enumerate() {
+            assert_eq!(template.index_of(name, 0, "-"), Some(index as u128), "name was {name:?}");
+        }
+        assert_eq!(template.index_of("{bolder}_0g", 0, ""), None);
+        assert_eq!(template.index_of("{bolder}_000", 0, ""), None);
+    }
+
+    #[test]
+    fn style_changes_the_case_of_each_slot_but_literals() {
+        let petnames = Petnames::new("bold", "", "ant");
+        let template = Template::new(&petnames, "the {adjective} {noun}!").unwrap();
+        let name = |style| template.namer().with_style(style).name_for_key(b"");
+        assert_eq!(name(Style::Pascal).as_deref(), Some("the Bold Ant!"));
+        assert_eq!(name(Style::Upper).as_deref(), Some("the BOLD ANT!"));
+    }
+
+    #[test]
+    fn entropy_bits_does_not_saturate() {
+        let petnames = Petnames::new("", "", "ant");
+        let pattern = "{hex:16}".repeat(3);
+        let template = Template::new(&petnames, &pattern).unwrap();
+        assert_eq!(template.cardinality(), u128::MAX);
+        assert_eq!(template.entropy_bits(0), 192.0);
+    }
+}
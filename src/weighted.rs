@@ -0,0 +1,287 @@
+//! Petnames whose words are chosen in proportion to their weights.
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{fmt, ops::Range};
+
+use rand::distr::{weighted::WeightedIndex, Distribution};
+
+use crate::{log2, Generator, List, Lists, Namer, Petnames, WordFinder, Words};
+
+/// A [`Generator`] like [`Petnames`], but in which each word is chosen with a
+/// probability proportional to its _weight_, e.g. how common it is.
+///
+/// Because names are not equally likely, there is no useful index of each
+/// name, so this does not implement [`Indexed`][`crate::Indexed`]; see
+/// [`entropy_bits`][`Self::entropy_bits`] instead of a cardinality. Words
+/// with a weight of zero are never chosen.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "default-rng")] {
+/// let weighted = petname::Weighted::new("fair:3 foul:1", "", "cat dog:0").unwrap();
+/// // Less than the 1 bit of choosing between two adjectives uniformly.
+/// assert!((weighted.entropy_bits(2) - 0.811).abs() < 0.001);
+/// let name = weighted.namer(2, "-").iter(&mut rand::rng()).next().unwrap();
+/// assert!(name == "fair-cat" || name == "foul-cat");
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weighted<'a> {
+    petnames: Petnames<'a>,
+    adjectives: Weights<'a>,
+    adverbs: Weights<'a>,
+    nouns: Weights<'a>,
+}
+
+/// The weights of the words in one list.
+#[derive(Clone, Debug, PartialEq)]
+struct Weights<'a> {
+    weights: Cow<'a, [f64]>,
+    /// For choosing a word, or `None` if no word has any weight.
+    index: Option<WeightedIndex<f64>>,
+}
+
+impl<'a> Weights<'a> {
+    fn new(weights: Cow<'a, [f64]>) -> Self {
+        let index = WeightedIndex::new(weights.iter().copied()).ok();
+        Self { weights, index }
+    }
+
+    fn choose(&self, rng: &mut dyn rand::Rng) -> Option<usize> {
+        self.index.as_ref().map(|index| index.sample(rng))
+    }
+
+    /// The collision entropy of a word chosen from this list: minus the log of
+    /// the chance that two words chosen independently are the same.
+    fn collision_bits(&self) -> f64 {
+        let total: f64 = self.weights.iter().sum();
+        -log2(self.weights.iter().map(|&weight| (weight / total) * (weight / total)).sum())
+    }
+
+    /// The Shannon entropy of a word chosen from this list.
+    fn entropy_bits(&self) -> f64 {
+        let total: f64 = self.weights.iter().sum();
+        let sum: f64 =
+            self.weights.iter().filter(|&&weight| weight > 0.0).map(|&weight| weight * log2(weight)).sum();
+        log2(total) - sum / total
+    }
+}
+
+impl<'a> Weighted<'a> {
+    /// Constructs a new [`Weighted`] from the given word lists.
+    ///
+    /// The words are extracted from the given strings by splitting on
+    /// whitespace, as with [`Petnames::new`]. Each word may be followed by a
+    /// colon and its weight, e.g. `fox:2.5`; a word without one has a weight
+    /// of 1.
+    pub fn new(adjectives: &'a str, adverbs: &'a str, nouns: &'a str) -> Result<Self, WeightsError> {
+        let (adjectives, adjective_weights) = parse(adjectives)?;
+        let (adverbs, adverb_weights) = parse(adverbs)?;
+        let (nouns, noun_weights) = parse(nouns)?;
+        Self::with_weights(
+            Petnames { adjectives: adjectives.into(), adverbs: adverbs.into(), nouns: nouns.into() },
+            adjective_weights,
+            adverb_weights,
+            noun_weights,
+        )
+    }
+
+    /// Give the words of `petnames` the given weights, one for each word in
+    /// the corresponding list.
+    pub fn with_weights(
+        petnames: Petnames<'a>,
+        adjectives: impl Into<Cow<'a, [f64]>>,
+        adverbs: impl Into<Cow<'a, [f64]>>,
+        nouns: impl Into<Cow<'a, [f64]>>,
+    ) -> Result<Self, WeightsError> {
+        let (adjectives, adverbs, nouns) = (adjectives.into(), adverbs.into(), nouns.into());
+        for (words, weights) in
+            [(&petnames.adjectives, &adjectives), (&petnames.adverbs, &adverbs), (&petnames.nouns, &nouns)]
+        {
+            if words.len() != weights.len() {
+                return Err(WeightsError::Mismatched);
+            }
+            if let Some((word, _)) = words.iter().zip(weights.iter()).find(|(_, weight)| !valid(**weight)) {
+                return Err(WeightsError::Invalid((*word).into()));
+            }
+        }
+        Ok(Self {
+            petnames,
+            adjectives: Weights::new(adjectives),
+            adverbs: Weights::new(adverbs),
+            nouns: Weights::new(nouns),
+        })
+    }
+
+    /// Give each word of `petnames` the weight returned by `weight`.
+    pub fn from_fn<F>(petnames: Petnames<'a>, mut weight: F) -> Result<Self, WeightsError>
+    where
+        F: FnMut(&str) -> f64,
+    {
+        let mut weights = |words: &Words| words.iter().map(|word| weight(word)).collect::<Vec<f64>>();
+        let (adjectives, adverbs, nouns) =
+            (weights(&petnames.adjectives), weights(&petnames.adverbs), weights(&petnames.nouns));
+        Self::with_weights(petnames, adjectives, adverbs, nouns)
+    }
+
+    /// The underlying word lists.
+    pub fn petnames(&self) -> &Petnames<'a> {
+        &self.petnames
+    }
+
+    /// Keep words matching a predicate, and their weights.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&str) -> bool,
+    {
+        fn retain<'a>(
+            words: &mut Words<'a>,
+            weights: &mut Weights<'a>,
+            predicate: &mut dyn FnMut(&str) -> bool,
+        ) {
+            let (kept, kept_weights): (Vec<&'a str>, Vec<f64>) =
+                words.iter().zip(weights.weights.iter()).filter(|(word, _)| predicate(word)).unzip();
+            *words = kept.into();
+            *weights = Weights::new(kept_weights.into());
+        }
+        retain(&mut self.petnames.adjectives, &mut self.adjectives, &mut predicate);
+        retain(&mut self.petnames.adverbs, &mut self.adverbs, &mut predicate);
+        retain(&mut self.petnames.nouns, &mut self.nouns, &mut predicate);
+    }
+
+    /// The effective entropy, in bits, of a name of `words` words: the
+    /// Shannon entropy of the words chosen, given their weights.
+    ///
+    /// This is at most the entropy of choosing uniformly, i.e.
+    /// [`Indexed::entropy_bits`][`crate::Indexed::entropy_bits`] for the
+    /// underlying [`Petnames`], and is the same when every word has the same
+    /// weight. Returns 0 when there are no names to choose from.
+    pub fn entropy_bits(&self, words: u8) -> f64 {
+        self.sum_over_lists(words, Weights::entropy_bits)
+    }
+
+    /// The collision entropy, in bits, of a name of `words` words: minus the
+    /// log of the chance that two names chosen independently are the same.
+    ///
+    /// This is at most [`entropy_bits`][`Self::entropy_bits`], and, unlike
+    /// it, says how many names can be chosen before two are likely to be the
+    /// same: about `2^(bits / 2)`. Returns 0 when there are no names to choose
+    /// from.
+    pub fn collision_bits(&self, words: u8) -> f64 {
+        self.sum_over_lists(words, Weights::collision_bits)
+    }
+
+    /// Create a [`Namer`] that generates petnames from these weighted word
+    /// lists.
+    pub fn namer<'b>(&'b self, words: u8, separator: &'b str) -> Namer<'b, Self> {
+        Namer::new(self, words, separator)
+    }
+
+    /// Sum `bits` of the list of each of `words` words, or 0 if any of those
+    /// lists has no word with a weight.
+    fn sum_over_lists(&self, words: u8, bits: fn(&Weights<'a>) -> f64) -> f64 {
+        let lists: Vec<&Weights<'a>> = Lists::new(words).map(|list| self.weights(list)).collect();
+        if lists.is_empty() || lists.iter().any(|weights| weights.index.is_none()) {
+            0.0
+        } else {
+            lists.into_iter().map(bits).sum()
+        }
+    }
+
+    fn weights(&self, list: List) -> &Weights<'a> {
+        match list {
+            List::Adverb => &self.adverbs,
+            List::Adjective => &self.adjectives,
+            List::Noun => &self.nouns,
+        }
+    }
+}
+
+impl Generator for Weighted<'_> {
+    /// As with [`Petnames`], the name _may_ contain fewer words than
+    /// requested if a list has no words, or none with any weight.
+    fn generate_into(&self, buf: &mut String, rng: &mut dyn rand::Rng, words: u8, separator: &str) {
+        let chosen = Lists::new(words).filter_map(|list| {
+            let index = self.weights(list).choose(rng)?;
+            Some(match list {
+                List::Adverb => self.petnames.adverbs[index],
+                List::Adjective => self.petnames.adjectives[index],
+                List::Noun => self.petnames.nouns[index],
+            })
+        });
+        for (position, word) in chosen.enumerate() {
+            if position > 0 {
+                buf.push_str(separator);
+            }
+            buf.push_str(word);
+        }
+    }
+
+    fn word_ranges(&self, name: &str, words: u8, separator: &str) -> Vec<Range<usize>> {
+        self.petnames.word_ranges(name, words, separator)
+    }
+
+    fn word_finder<'s>(&'s self, words: u8, separator: &'s str) -> WordFinder<'s> {
+        self.petnames.word_finder(words, separator)
+    }
+}
+
+/// Split `words` on whitespace into words and their weights.
+fn parse(words: &str) -> Result<(Vec<&str>, Vec<f64>), WeightsError> {
+    words
+        .split_whitespace()
+        .map(|token| match token.rsplit_once(':') {
+            None => Ok((token, 1.0)),
+            Some((word, weight)) => match weight.parse() {
+                Ok(weight) if valid(weight) => Ok((word, weight)),
+                Ok(_) => Err(WeightsError::Invalid(word.into())),
+                Err(_) => Err(WeightsError::Unparsable(token.into())),
+            },
+        })
+        .collect()
+}
+
+fn valid(weight: f64) -> bool {
+    weight.is_finite() && weight >= 0.0
+}
+
+/// The reason that weights could not be given to words.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WeightsError {
+    /// The weight after the colon in this token is not a number.
+    Unparsable(String),
+    /// The weight of this word is negative, infinite, or not a number.
+    Invalid(String),
+    /// A list of weights is not the same length as its list of words.
+    Mismatched,
+}
+
+impl fmt::Display for WeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unparsable(token) => write!(f, "weight is not a number: {token}"),
+            Self::Invalid(word) => write!(f, "weight must be finite and not negative: {word}"),
+            Self::Mismatched => write!(f, "there must be one weight for each word"),
+        }
+    }
+}
+
+impl core::error::Error for WeightsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, WeightsError};
+
+    #[test]
+    fn parse_reads_optional_weights() {
+        assert_eq!(
+            parse(" fox:2.5 owl\n yak:0 "),
+            Ok((alloc::vec!["fox", "owl", "yak"], alloc::vec![2.5, 1.0, 0.0]))
+        );
+        assert_eq!(parse("fox:lots"), Err(WeightsError::Unparsable("fox:lots".into())));
+        assert_eq!(parse("fox:-1"), Err(WeightsError::Invalid("fox".into())));
+        assert_eq!(parse("fox:inf"), Err(WeightsError::Invalid("fox".into())));
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
-use petname::{Alliterations, Petnames};
+use petname::{Alliterations, Folding, Indexed, Petnames, Sampling};
+use rand::SeedableRng;
 
 mod mocks;
 
@@ -70,3 +71,118 @@ fn alliterations_iter_yields_nothing_when_empty() {
     let mut names = namer.iter(&mut rng);
     assert_eq!(None, names.next());
 }
+
+#[test]
+fn alliterations_unique_iter_yields_every_name_once() {
+    let mut rng = mocks::StepRng::new(0, 1);
+    let petnames = Petnames::new("able apt bold", "burly curly", "ant bee cow");
+    let alliterations: Alliterations = petnames.into();
+    let names: HashSet<String> = alliterations.namer(2, " ").unique_iter(&mut rng).collect();
+    let expected: HashSet<String> = ["able ant", "apt ant", "bold bee"].map(String::from).into();
+    assert_eq!(expected, names);
+}
+
+#[test]
+fn alliterations_name_at_and_index_of_are_inverses() {
+    let petnames = Petnames::new("able apt bold", "burly curly", "ant bee cow");
+    let alliterations: Alliterations = petnames.into();
+    for words in 1..=3 {
+        for index in 0..alliterations.cardinality(words) {
+            let name = alliterations.name_at(index, words, "-").unwrap();
+            assert_eq!(alliterations.index_of(&name, words, "-"), Some(index), "name was {name:?}");
+        }
+    }
+    assert_eq!(alliterations.index_of("able-bee", 2, "-"), None);
+}
+
+#[test]
+fn alliterations_entropy_bits_counts_every_group() {
+    // 2 names beginning with "a", 1 beginning with "b", 1 beginning with "c".
+    let petnames = Petnames::new("able apt bold cool", "burly curly", "ant bee cow");
+    let alliterations: Alliterations = petnames.into();
+    assert_eq!(alliterations.cardinality(2), 4);
+    assert_eq!(alliterations.entropy_bits(2), 2.0);
+    assert_eq!(alliterations.namer_for_entropy(1.0, "-").map(|namer| namer.words()), Some(1));
+    assert_eq!(alliterations.namer_for_entropy(2.0, "-").map(|namer| namer.words()), Some(2));
+}
+
+#[test]
+fn alliterations_entropy_bits_does_not_saturate() {
+    // 2 choices for each of 200 words, so 2^200 names, far more than a u128 can count.
+    let petnames = Petnames::new("able apt", "ably aptly", "ant ape");
+    let alliterations: Alliterations = petnames.into();
+    assert_eq!(alliterations.cardinality(200), u128::MAX);
+    assert!((alliterations.entropy_bits(200) - 200.0).abs() < 1e-9);
+}
+
+#[test]
+fn alliterations_sampling_chooses_groups_or_names_equally() {
+    // 4 names beginning with "a", 1 beginning with "b".
+    let petnames = Petnames::new("able apt arid awful bold", "", "ant bee");
+    let count_bold_bee = |sampling| {
+        let alliterations = Alliterations::from(petnames.clone()).with_sampling(sampling);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let namer = alliterations.namer(2, "-");
+        namer.iter(&mut rng).take(1000).filter(|name| name == "bold-bee").count()
+    };
+    // About half of names are from the "b" group when choosing by group…
+    assert!((400..600).contains(&count_bold_bee(Sampling::ByGroup)));
+    // … but only about 1 in 5 when every name is equally likely.
+    assert!((150..250).contains(&count_bold_bee(Sampling::Uniform)));
+}
+
+#[test]
+fn alliterations_group_by_any_key() {
+    let petnames = Petnames::new("able bold", "burly curly", "ant bee bison cow");
+    let alliterations = Alliterations::group_by(petnames, |word| word.chars().count());
+    // No noun has 4 letters, so neither adjective is in a group.
+    let alliterations_expected: Alliterations<usize> =
+        [(3, Petnames::new("", "", "ant bee cow")), (5, Petnames::new("", "burly curly", "bison"))].into();
+    assert_eq!(alliterations_expected, alliterations);
+    for words in 1..=3 {
+        for index in 0..alliterations.cardinality(words) {
+            let name = alliterations.name_at(index, words, "-").unwrap();
+            assert_eq!(alliterations.index_of(&name, words, "-"), Some(index), "name was {name:?}");
+        }
+    }
+}
+
+#[test]
+fn alliterations_group_by_first_letter_is_from() {
+    let petnames = Petnames::new("able bold", "burly curly", "ant bee cow");
+    let grouped = Alliterations::group_by(petnames.clone(), |word| word.chars().next().unwrap());
+    assert_eq!(grouped, Alliterations::from(petnames));
+}
+
+#[test]
+fn alliterations_from_petnames_does_not_fold() {
+    let petnames = Petnames::new("Émile emerald", "", "Ember emu ibis İnci");
+    let alliterations: Alliterations = petnames.into();
+    let letters: Vec<char> = alliterations.groups().map(|(letter, _)| letter).collect();
+    assert_eq!(letters, vec!['E', 'e', 'i', 'İ']);
+}
+
+#[test]
+fn alliterations_by_first_letter_ignores_case_and_accents() {
+    let petnames = Petnames::new("Émile E\u{301}mile emerald", "", "Ember ibis İnci");
+    let alliterations = Alliterations::by_first_letter(petnames, Folding::default());
+    let groups: Vec<(char, Petnames)> =
+        alliterations.groups().map(|(letter, group)| (letter, group.clone())).collect();
+    assert_eq!(
+        groups,
+        vec![
+            ('e', Petnames::new("Émile E\u{301}mile emerald", "", "Ember")),
+            ('i', Petnames::new("", "", "ibis İnci")),
+        ]
+    );
+}
+
+#[test]
+fn alliterations_by_first_letter_in_turkish() {
+    let petnames = Petnames::new("çok cömert", "şen sakin", "Çınar ceylan Şahin serçe");
+    let alliterations = Alliterations::by_first_letter(petnames, Folding::Turkish);
+    let letters: Vec<char> = alliterations.groups().map(|(letter, _)| letter).collect();
+    assert_eq!(letters, vec!['c', 's', 'ç', 'ş']);
+    assert_eq!(alliterations.cardinality(3), 0);
+    assert_eq!(alliterations.cardinality(2), 2);
+}
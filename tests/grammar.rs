@@ -0,0 +1,68 @@
+use petname::{Grammar, GrammarError, Indexed, Petnames};
+
+mod mocks;
+
+#[test]
+fn grammar_from_petnames_makes_the_same_names() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow");
+    let grammar: Grammar = petnames.clone().into();
+    for words in 0..=4 {
+        assert_eq!(grammar.cardinality(words), petnames.cardinality(words));
+        assert_eq!(grammar.entropy_bits(words), petnames.entropy_bits(words));
+        for index in 0..petnames.cardinality(words) {
+            assert_eq!(grammar.name_at(index, words, "-"), petnames.name_at(index, words, "-"));
+        }
+    }
+}
+
+#[test]
+fn grammar_name_at_and_index_of_are_inverses() {
+    let grammar = Grammar::new("verb preposition* place")
+        .unwrap()
+        .with_words("verb", "run jump")
+        .with_words("preposition", "to over under")
+        .with_words("place", "moon sea");
+    for words in 1..=4 {
+        for index in 0..grammar.cardinality(words) {
+            let name = grammar.name_at(index, words, "-").unwrap();
+            assert_eq!(grammar.index_of(&name, words, "-"), Some(index), "name was {name:?}");
+        }
+    }
+    assert_eq!(grammar.name_at(0, 4, " ").as_deref(), Some("run to to moon"));
+    assert_eq!(grammar.index_of("moon-run", 2, "-"), None);
+}
+
+#[test]
+fn grammar_without_a_repeating_slot_has_no_longer_names() {
+    let grammar =
+        Grammar::new("color animal").unwrap().with_words("color", "red").with_words("animal", "fox");
+    assert_eq!(grammar.cardinality(2), 1);
+    assert_eq!(grammar.cardinality(3), 0);
+    let mut rng = mocks::StepRng::new(0, 1);
+    assert_eq!(grammar.namer(3, "-").iter(&mut rng).next(), None);
+}
+
+#[test]
+fn grammar_generate_uses_slots_in_order() {
+    let grammar =
+        Grammar::new("color animal").unwrap().with_words("color", "red").with_words("animal", "fox");
+    let mut rng = mocks::StepRng::new(0, 1);
+    assert_eq!(grammar.namer(2, "_").iter(&mut rng).next().as_deref(), Some("red_fox"));
+    assert_eq!(grammar.namer(1, "_").iter(&mut rng).next().as_deref(), Some("fox"));
+}
+
+#[test]
+fn grammar_retain_applies_to_every_list() {
+    let mut grammar =
+        Grammar::new("color animal").unwrap().with_words("color", "red blue").with_words("animal", "fox bat");
+    grammar.retain(|word| word.starts_with('b'));
+    assert_eq!(grammar.list("color"), Some(&["blue"][..]));
+    assert_eq!(grammar.list("animal"), Some(&["bat"][..]));
+    assert_eq!(grammar.list("place"), None);
+}
+
+#[test]
+fn grammar_new_rejects_invalid_slots() {
+    assert_eq!(Grammar::new(""), Err(GrammarError::NoSlots));
+    assert_eq!(Grammar::new("a* b*"), Err(GrammarError::ManyRepeats("b".into())));
+}
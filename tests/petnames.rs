@@ -1,4 +1,8 @@
-use petname::Petnames;
+use std::collections::BTreeSet;
+
+use petname::lang::english::{ParseError, ParsedWord};
+use petname::{Alphabet, Indexed, Petnames, Suffix};
+use rand::SeedableRng;
 
 mod mocks;
 
@@ -84,3 +88,260 @@ fn petnames_generate_into_works() {
     petnames.namer(3, " ").generate_into(&mut buf, &mut rng);
     assert_eq!("adv adj noun", &buf);
 }
+
+#[test]
+fn petnames_unique_iter_yields_every_name_once() {
+    let mut rng = mocks::StepRng::new(0, 1);
+    let petnames = Petnames::new("able bold", "very", "ant bee cow");
+    let names: Vec<String> = petnames.namer(3, "-").unique_iter(&mut rng).collect();
+    let distinct: std::collections::HashSet<&String> = names.iter().collect();
+    assert_eq!(petnames.cardinality(3), 6);
+    assert_eq!(names.len(), 6);
+    assert_eq!(distinct.len(), 6);
+    assert!(names.iter().all(|name| name.starts_with("very-")));
+}
+
+#[test]
+fn petnames_unique_iter_yields_nothing_when_empty() {
+    let mut rng = mocks::StepRng::new(0, 1);
+    let petnames = Petnames::new("able", "", "ant");
+    assert_eq!(None, petnames.namer(3, "-").unique_iter(&mut rng).next());
+}
+
+#[test]
+fn petnames_name_at_and_index_of_are_inverses() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow");
+    for words in 1..=4 {
+        for index in 0..petnames.cardinality(words) {
+            let name = petnames.name_at(index, words, "-").unwrap();
+            assert_eq!(petnames.index_of(&name, words, "-"), Some(index), "name was {name:?}");
+        }
+        assert_eq!(petnames.name_at(petnames.cardinality(words), words, "-"), None);
+    }
+}
+
+#[test]
+fn petnames_name_at_varies_last_word_fastest() {
+    let petnames = Petnames::new("able bold", "", "ant bee cow");
+    assert_eq!(petnames.name_at(0, 2, " ").as_deref(), Some("able ant"));
+    assert_eq!(petnames.name_at(1, 2, " ").as_deref(), Some("able bee"));
+    assert_eq!(petnames.name_at(3, 2, " ").as_deref(), Some("bold ant"));
+}
+
+#[test]
+fn petnames_index_of_rejects_unknown_names() {
+    let petnames = Petnames::new("able bold", "very", "ant bee cow");
+    assert_eq!(petnames.index_of("able-dog", 2, "-"), None);
+    assert_eq!(petnames.index_of("ant-able", 2, "-"), None);
+    assert_eq!(petnames.index_of("able-ant", 2, "_"), None);
+    assert_eq!(petnames.index_of("", 1, "-"), None);
+    assert_eq!(petnames.index_of("", 0, "-"), None);
+}
+
+#[test]
+fn petnames_index_of_is_relative_to_the_number_of_words() {
+    // "bee" and "able-bee" both have index 0 among names of their own length.
+    let petnames = Petnames::new("able", "", "bee");
+    assert_eq!(petnames.index_of("bee", 1, "-"), Some(0));
+    assert_eq!(petnames.index_of("able-bee", 2, "-"), Some(0));
+    assert_eq!(petnames.index_of("bee", 2, "-"), None);
+    assert_eq!(petnames.index_of("able-bee", 1, "-"), None);
+}
+
+#[test]
+fn petnames_index_of_handles_separators_within_words() {
+    let petnames = Petnames::new("x-ray able", "", "ant fish-eye");
+    assert_eq!(petnames.index_of("x-ray-fish-eye", 2, "-"), petnames.index_of("x-ray fish-eye", 2, " "));
+    assert_eq!(
+        petnames.name_at(petnames.index_of("x-ray-fish-eye", 2, "-").unwrap(), 2, "-").as_deref(),
+        Some("x-ray-fish-eye")
+    );
+}
+
+#[test]
+fn petnames_index_of_handles_empty_separator() {
+    let petnames = Petnames::new("able bold", "very", "ant bee cow");
+    assert_eq!(petnames.index_of("veryboldcow", 3, ""), petnames.index_of("very-bold-cow", 3, "-"));
+}
+
+#[test]
+fn petnames_name_for_key_is_stable() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow");
+    // These must never change: names derived from keys are stable.
+    assert_eq!(petnames.name_for_key(b"", 3, "-").as_deref(), Some("quite-bold-ant"));
+    assert_eq!(petnames.name_for_key(b"tenant-42", 3, "-").as_deref(), Some("very-able-cow"));
+    assert_eq!(petnames.name_for_key(b"tenant-42", 3, "-"), petnames.name_for_key(b"tenant-42", 3, "-"));
+}
+
+#[test]
+fn petnames_name_for_key_is_none_when_empty() {
+    let petnames = Petnames::new("", "", "");
+    assert_eq!(petnames.name_for_key(b"key", 2, "-"), None);
+}
+
+#[test]
+fn petnames_entropy_bits_does_not_saturate() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow cat");
+    assert_eq!(petnames.entropy_bits(0), 0.0);
+    assert_eq!(petnames.entropy_bits(1), 2.0);
+    assert_eq!(petnames.entropy_bits(3), 4.0);
+    // 2^255 * 4 names: far more than `cardinality` can count.
+    assert_eq!(petnames.cardinality(u8::MAX), u128::MAX);
+    assert_eq!(petnames.entropy_bits(u8::MAX), 256.0);
+}
+
+#[test]
+fn petnames_namer_for_entropy_picks_fewest_words() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow cat");
+    assert_eq!(petnames.namer_for_entropy(0.0, "-").map(|namer| namer.words()), Some(1));
+    assert_eq!(petnames.namer_for_entropy(2.5, "-").map(|namer| namer.words()), Some(2));
+    assert_eq!(petnames.namer_for_entropy(3.0, "-").map(|namer| namer.words()), Some(2));
+    assert_eq!(petnames.namer_for_entropy(200.0, "-").map(|namer| namer.words()), Some(199));
+    assert!(petnames.namer_for_entropy(300.0, "-").is_none());
+    // Without adverbs there are never more than two words.
+    let petnames = Petnames::new("able bold", "", "ant bee cow cat");
+    assert!(petnames.namer_for_entropy(4.0, "-").is_none());
+}
+
+#[test]
+fn petnames_namer_with_suffix_counts_suffixes() {
+    let petnames = Petnames::new("able bold", "", "ant");
+    let namer = petnames.namer(2, "-").with_suffix(Suffix::new(Alphabet::Digits, 2));
+    assert_eq!(namer.cardinality(), 200);
+    assert_eq!(namer.entropy_bits(), 1.0 + Suffix::new(Alphabet::Digits, 2).entropy_bits());
+    let mut rng = mocks::StepRng::new(0, 1);
+    let names: BTreeSet<String> = namer.unique_iter(&mut rng).collect();
+    assert_eq!(names.len(), 200);
+    assert!(names.contains("able-ant-00") && names.contains("bold-ant-99"));
+    // No names, so no suffixes either.
+    let petnames = Petnames::new("", "", "");
+    let namer = petnames.namer(2, "-").with_suffix(Suffix::new(Alphabet::Digits, 2));
+    assert_eq!(namer.cardinality(), 0);
+    assert_eq!(namer.iter(&mut rng).next(), None);
+}
+
+#[test]
+fn petnames_namer_name_for_key_with_suffix() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow");
+    let namer = petnames.namer(3, "-");
+    // Without a suffix, names are the same as from the generator.
+    assert_eq!(namer.name_for_key(b"tenant-42"), petnames.name_for_key(b"tenant-42", 3, "-"));
+    let namer = namer.with_suffix(Suffix::new(Alphabet::Consonants, 4));
+    let name = namer.name_for_key(b"tenant-42").unwrap();
+    assert_eq!(namer.name_for_key(b"tenant-42").as_deref(), Some(name.as_str()));
+    let (name, suffix) = name.rsplit_once('-').unwrap();
+    assert!(petnames.index_of(name, 3, "-").is_some());
+    assert!(suffix.len() == 4 && suffix.chars().all(|c| Alphabet::Consonants.chars().contains(c)));
+}
+
+#[test]
+fn petnames_collision_probability_uses_birthday_bound() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow cat");
+    assert_eq!(petnames.collision_probability(3, 0), 0.0);
+    assert_eq!(petnames.collision_probability(3, 1), 0.0);
+    assert_eq!(petnames.collision_probability(3, 2), 1.0 / 16.0);
+    assert_eq!(petnames.collision_probability(3, 5), 10.0 / 16.0);
+    assert_eq!(petnames.collision_probability(3, 17), 1.0);
+    assert_eq!(Petnames::new("", "", "").collision_probability(2, 2), 1.0);
+}
+
+/// Every name of `words` words, repeated words and all.
+fn all_names(petnames: &Petnames, words: u8) -> Vec<String> {
+    (0..petnames.cardinality(words)).map(|index| petnames.name_at(index, words, "-").unwrap()).collect()
+}
+
+fn has_distinct_words(name: &str) -> bool {
+    let words: Vec<&str> = name.split('-').collect();
+    words.iter().collect::<BTreeSet<_>>().len() == words.len()
+}
+
+#[test]
+fn petnames_distinct_cardinality_counts_names_without_repeated_words() {
+    // Words are shared between every pair of lists.
+    let petnames = Petnames::new("able bold gold", "very bold quite ant", "ant bee gold");
+    let distinct = petnames.distinct();
+    for words in 0..=6 {
+        let expected = all_names(&petnames, words).iter().filter(|name| has_distinct_words(name)).count();
+        assert_eq!(distinct.cardinality(words), expected as u128, "words = {words}");
+    }
+}
+
+#[test]
+fn petnames_distinct_name_at_and_index_of_are_inverses() {
+    let petnames = Petnames::new("able bold gold", "very bold quite ant", "ant bee gold");
+    let distinct = petnames.distinct();
+    for words in 1..=5 {
+        let names: Vec<String> = (0..distinct.cardinality(words))
+            .map(|index| distinct.name_at(index, words, "-").unwrap())
+            .collect();
+        let expected: BTreeSet<String> =
+            all_names(&petnames, words).into_iter().filter(|name| has_distinct_words(name)).collect();
+        assert_eq!(names.iter().cloned().collect::<BTreeSet<_>>(), expected, "words = {words}");
+        for (index, name) in names.iter().enumerate() {
+            assert_eq!(distinct.index_of(name, words, "-"), Some(index as u128), "name was {name:?}");
+        }
+    }
+    assert_eq!(distinct.index_of("very-very-able-ant", 4, "-"), None);
+    assert_eq!(distinct.index_of("gold-gold", 2, "-"), None);
+}
+
+#[test]
+fn petnames_distinct_never_repeats_a_word() {
+    let petnames = Petnames::new("able gold", "very quite", "ant gold");
+    let distinct = petnames.distinct();
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+    let names: BTreeSet<String> = distinct.namer(4, "-").iter(&mut rng).take(500).collect();
+    assert_eq!(names.len() as u128, distinct.cardinality(4));
+    assert!(names.iter().all(|name| has_distinct_words(name)));
+    // Only 2 adverbs, so at most 2 adverbs in a name.
+    let names: BTreeSet<String> = distinct.namer(5, "-").iter(&mut rng).take(10).collect();
+    assert!(names.iter().all(|name| name.split('-').count() == 4));
+    assert_eq!(distinct.cardinality(5), 0);
+}
+
+#[test]
+fn petnames_distinct_chooses_every_name_equally() {
+    // Drawing the adjective first, then any other noun, gives "a-c" half the time.
+    let petnames = Petnames::new("a b", "", "a c");
+    let distinct = petnames.distinct();
+    assert_eq!(distinct.cardinality(2), 3);
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+    let names: Vec<String> = distinct.namer(2, "-").iter(&mut rng).take(3000).collect();
+    for name in ["a-c", "b-a", "b-c"] {
+        let count = names.iter().filter(|&other| other == name).count();
+        assert!((900..1100).contains(&count), "{name} appeared {count} times");
+    }
+}
+
+#[test]
+fn petnames_parse_returns_words_and_indices() {
+    let petnames = Petnames::new("able bold", "very quite", "ant bee cow");
+    let parsed = petnames.parse("quite-very-bold-cow", "-").unwrap();
+    assert_eq!(parsed.words(), 4);
+    assert_eq!(
+        parsed.adverbs,
+        [ParsedWord { word: "quite", index: 1 }, ParsedWord { word: "very", index: 0 }]
+    );
+    assert_eq!(parsed.adjective, Some(ParsedWord { word: "bold", index: 1 }));
+    assert_eq!(parsed.noun, ParsedWord { word: "cow", index: 2 });
+    let parsed = petnames.parse("bee", "-").unwrap();
+    assert_eq!((parsed.words(), parsed.adjective), (1, None));
+}
+
+#[test]
+fn petnames_parse_explains_errors() {
+    let petnames = Petnames::new("able bold", "very", "ant bee cow");
+    assert_eq!(petnames.parse("", "-"), Err(ParseError::Empty));
+    assert_eq!(petnames.parse("able-dog", "-"), Err(ParseError::UnknownWord("dog".into())));
+    assert_eq!(petnames.parse("ant-able", "-"), Err(ParseError::Unrecognised));
+    assert_eq!(petnames.parse("able", "-"), Err(ParseError::Unrecognised));
+    assert_eq!(petnames.parse("abledog", ""), Err(ParseError::Unrecognised));
+}
+
+#[test]
+fn petnames_parse_detects_ambiguity() {
+    let petnames = Petnames::new("x x-ray", "", "ray ray-gun gun");
+    assert_eq!(petnames.parse("x-ray-gun", "-"), Err(ParseError::Ambiguous));
+    assert!(petnames.parse("x-ray-gun", " ").is_err());
+    assert!(petnames.parse("x-ray gun", " ").is_ok());
+}
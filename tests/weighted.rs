@@ -0,0 +1,74 @@
+use petname::{Indexed, Petnames, Weighted, WeightsError};
+
+mod mocks;
+
+#[test]
+fn weighted_never_chooses_words_without_weight() {
+    let weighted = Weighted::new("able:0 bold", "", "ant bee:0 cow:0").unwrap();
+    let mut rng = mocks::StepRng::new(0, 0x1234_5678_9abc_def1);
+    for name in weighted.namer(2, "-").iter(&mut rng).take(100) {
+        assert_eq!(name, "bold-ant");
+    }
+    assert_eq!(weighted.entropy_bits(2), 0.0);
+}
+
+#[test]
+fn weighted_entropy_bits_with_equal_weights_matches_petnames() {
+    let petnames = Petnames::new("able bold", "very quite really", "ant bee cow");
+    let weighted = Weighted::from_fn(petnames.clone(), |_| 7.0).unwrap();
+    for words in 0..=4 {
+        assert!((weighted.entropy_bits(words) - petnames.entropy_bits(words)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn weighted_entropy_bits_is_less_with_unequal_weights() {
+    let petnames = Petnames::new("able bold", "", "ant bee cow");
+    let weighted =
+        Weighted::from_fn(petnames.clone(), |word| if word.starts_with('a') { 10.0 } else { 1.0 }).unwrap();
+    assert!(weighted.entropy_bits(2) > 0.0);
+    assert!(weighted.entropy_bits(2) < petnames.entropy_bits(2));
+    assert!(weighted.collision_bits(2) > 0.0);
+    assert!(weighted.collision_bits(2) < weighted.entropy_bits(2));
+}
+
+#[test]
+fn weighted_collision_bits_with_equal_weights_matches_petnames() {
+    let petnames = Petnames::new("able bold", "very quite really", "ant bee cow");
+    let weighted = Weighted::from_fn(petnames.clone(), |_| 7.0).unwrap();
+    for words in 0..=4 {
+        assert!((weighted.collision_bits(words) - petnames.entropy_bits(words)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn weighted_with_weights_checks_weights() {
+    let petnames = Petnames::new("able bold", "", "ant");
+    assert_eq!(
+        Weighted::with_weights(petnames.clone(), vec![1.0], vec![], vec![1.0]),
+        Err(WeightsError::Mismatched)
+    );
+    assert_eq!(
+        Weighted::with_weights(petnames, vec![1.0, f64::NAN], vec![], vec![1.0]),
+        Err(WeightsError::Invalid("bold".into()))
+    );
+    assert_eq!(Weighted::new("able:x", "", ""), Err(WeightsError::Unparsable("able:x".into())));
+}
+
+#[test]
+fn weighted_retain_keeps_weights() {
+    let mut weighted = Weighted::new("able:1 bold:0", "", "ant:0 bee:2").unwrap();
+    weighted.retain(|word| word != "able" && word != "ant");
+    assert_eq!(weighted.petnames(), &Petnames::new("bold", "", "bee"));
+    // The remaining adjective has no weight, so no adjective can be chosen.
+    let mut rng = mocks::StepRng::new(0, 1);
+    assert_eq!(weighted.namer(2, "-").iter(&mut rng).next().as_deref(), Some("bee"));
+}
+
+#[test]
+fn weighted_dedupe_phonetically_matches_petnames() {
+    let mut petnames = Petnames::new("bare fast", "fast fast", "bear cat cut knight night");
+    let mut weighted = Weighted::from_fn(petnames.clone(), |_| 1.0).unwrap();
+    assert_eq!(weighted.dedupe_phonetically(), petnames.dedupe_phonetically());
+    assert_eq!(weighted.petnames(), &petnames);
+}